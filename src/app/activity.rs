@@ -0,0 +1,39 @@
+use chrono::Local;
+use std::collections::VecDeque;
+
+const MINUTES: usize = 60;
+
+/// The most items running at once, per minute, over the last hour.
+#[derive(Default)]
+pub struct Activity {
+    minute: i64,
+    samples: VecDeque<u64>,
+}
+
+impl Activity {
+    pub fn record(&mut self, running: u64) {
+        let minute = Local::now().timestamp() / 60;
+        if self.samples.is_empty() || minute != self.minute {
+            // Minutes without a tick, e.g. while suspended, count as idle.
+            let passed = if self.samples.is_empty() {
+                1
+            } else {
+                (minute - self.minute).max(1).min(MINUTES as i64)
+            };
+            for _ in 0..passed {
+                self.samples.push_back(0);
+            }
+            while self.samples.len() > MINUTES {
+                self.samples.pop_front();
+            }
+            self.minute = minute;
+        }
+        if let Some(last) = self.samples.back_mut() {
+            *last = (*last).max(running);
+        }
+    }
+
+    pub fn samples(&self) -> Vec<u64> {
+        self.samples.iter().copied().collect()
+    }
+}
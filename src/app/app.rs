@@ -1,92 +1,1090 @@
+use crate::app::activity::Activity;
+use crate::app::as_of::AsOfView;
+use crate::app::bin::Deleted;
+use crate::app::clipboard;
+use crate::app::config::{Config, DurationPrecision, LeftAction, TomlFormat};
+use crate::app::confirm::Confirm;
+use crate::app::csv_import::CsvPreview;
+use crate::app::due::parse_relative;
+use crate::app::freeze::Frozen;
+use crate::app::group_by::{GroupBy, Row};
+use crate::app::import::ImportPreview;
+use crate::app::overview::Overview;
+use crate::app::review::Review;
+use crate::app::scratchpad::ScratchpadView;
 use crate::app::stateful_list::{Direction as ListDirection, StatefulList};
-use chrono::{DateTime, Duration, Local};
+use crate::app::tags::TagView;
+use crate::app::timebox::Timebox;
+use crate::app::undo::Operation;
+use crate::app::workspace::DB_PATH;
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Weekday};
 use crossterm::event::{KeyCode, KeyModifiers};
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::Instant;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Spans, Text},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{
+        Block, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph, Sparkline, Wrap,
+    },
     Frame,
 };
 
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum SortMode {
+    #[default]
+    Manual,
+    Title,
+    Duration,
+    Due,
+    Points,
+}
+
+impl SortMode {
+    fn next(self) -> SortMode {
+        match self {
+            SortMode::Manual => SortMode::Title,
+            SortMode::Title => SortMode::Duration,
+            SortMode::Duration => SortMode::Due,
+            SortMode::Due => SortMode::Points,
+            SortMode::Points => SortMode::Manual,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SortMode::Manual => "",
+            SortMode::Title => "[by title] ",
+            SortMode::Duration => "[by duration] ",
+            SortMode::Due => "[by due date] ",
+            SortMode::Points => "[by points] ",
+        }
+    }
+}
+
+/// How the item card splits its height between description and status.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum CardLayout {
+    #[default]
+    DescHeavy,
+    Even,
+    StatusHeavy,
+    StatusOnly,
+}
+
+impl CardLayout {
+    fn next(self) -> CardLayout {
+        match self {
+            CardLayout::DescHeavy => CardLayout::Even,
+            CardLayout::Even => CardLayout::StatusHeavy,
+            CardLayout::StatusHeavy => CardLayout::StatusOnly,
+            CardLayout::StatusOnly => CardLayout::DescHeavy,
+        }
+    }
+
+    fn constraints(self) -> [Constraint; 2] {
+        match self {
+            CardLayout::DescHeavy => [Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)],
+            CardLayout::Even => [Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)],
+            CardLayout::StatusHeavy => [Constraint::Ratio(1, 4), Constraint::Ratio(3, 4)],
+            CardLayout::StatusOnly => [Constraint::Length(0), Constraint::Min(0)],
+        }
+    }
+}
+
+/// What rows of items with a target show next to the title.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum RowTimeMode {
+    #[default]
+    Elapsed,
+    Remaining,
+}
+
+impl RowTimeMode {
+    fn next(self) -> RowTimeMode {
+        match self {
+            RowTimeMode::Elapsed => RowTimeMode::Remaining,
+            RowTimeMode::Remaining => RowTimeMode::Elapsed,
+        }
+    }
+
+    /// The row's time for `item`, `None` without a target.
+    fn label(self, item: &Item, precision: DurationPrecision) -> Option<String> {
+        let target = item.target.filter(|_| item.is_task())?;
+        Some(match self {
+            RowTimeMode::Elapsed => format!(" ({})", format_duration(item.duration, precision)),
+            RowTimeMode::Remaining if item.duration > target => format!(
+                " ({} over)",
+                format_duration(item.duration - target, precision)
+            ),
+            RowTimeMode::Remaining => format!(
+                " ({} left)",
+                format_duration(target - item.duration, precision)
+            ),
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct GroupList<T> {
+    /// Stays the same when the list is renamed or moved, see `App::new_list`.
+    #[serde(default)]
+    pub id: u64,
     pub name: String,
+    #[serde(default)]
+    pub sort_mode: SortMode,
+    #[serde(default)]
+    pub hourly_rate: Option<f64>,
+    #[serde(default)]
+    pub currency: String,
+    /// Time each item gets while the list runs as a timebox.
+    #[serde(default)]
+    pub timebox_minutes: Option<i64>,
+    /// How many items may be started but not done at the same time.
+    #[serde(default)]
+    pub wip_limit: Option<usize>,
+    /// Color name like "cyan" or "#ff8800" for the list's name.
+    #[serde(default)]
+    pub color: Option<String>,
+    /// A single character or emoji shown before the list's name.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Minutes item times are rounded to, to the nearest, in reports and the
+    /// list overview. Stored durations stay exact.
+    #[serde(default)]
+    pub display_rounding: Option<i64>,
+    /// Code of the project in an outside tool, for items without their own.
+    #[serde(default)]
+    pub project_code: Option<String>,
+    /// Minutes planned for the list each week, see the planning view.
+    #[serde(default)]
+    pub weekly_budget: Option<i64>,
+    /// Shown with just its name in the lists, without totals or counts.
+    #[serde(default)]
+    pub collapsed: bool,
+    /// Items paused together by `toggle_list_timers`, to resume them later.
+    #[serde(default)]
+    pub paused_ids: Vec<u64>,
     pub list: StatefulList<T>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ItemKind {
+    #[default]
+    Task,
+    Divider,
+}
+
+/// Whether an item's time counts up from zero or down from its target.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum TimerMode {
+    #[default]
+    CountUp,
+    CountDown,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Subtask {
+    pub title: String,
+    pub done: bool,
+}
+
+/// A separately timed work stream of an item, counting into its duration.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct SubTimer {
+    pub name: String,
+    pub duration: i64,
+    pub running: bool,
+}
+
+/// A note left when an item was marked done.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Stop {
+    pub at: DateTime<Local>,
+    /// The item's duration at that point.
+    pub duration: i64,
+    #[serde(default)]
+    pub note: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Item {
+    #[serde(default)]
+    pub id: u64,
     pub title: String,
     pub desc: String,
     pub start_at: Option<DateTime<Local>>,
     pub end_at: Option<DateTime<Local>>,
     pub duration: i64,
     pub paused: bool,
+    #[serde(default)]
+    pub target: Option<i64>,
+    #[serde(default)]
+    pub auto_stop_at_target: bool,
+    #[serde(default)]
+    pub kind: ItemKind,
+    #[serde(default)]
+    pub mode: TimerMode,
+    #[serde(default)]
+    pub due_at: Option<DateTime<Local>>,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// URL or file path related to the item.
+    #[serde(default)]
+    pub link: Option<String>,
+    /// Estimated effort in story points.
+    #[serde(default)]
+    pub points: Option<u32>,
+    /// Day the `tracked_today` time was logged on.
+    #[serde(default)]
+    pub tracked_on: Option<NaiveDate>,
+    #[serde(default)]
+    pub tracked_today: i64,
+    /// Manual progress in percent, for items without subtasks.
+    #[serde(default)]
+    pub progress: u8,
+    /// Day the item was picked as a focus, it only counts on that day.
+    #[serde(default)]
+    pub focus_date: Option<NaiveDate>,
+    /// Set once the duration passed the target, so crossing it is only
+    /// announced once.
+    #[serde(default)]
+    pub over_estimate: bool,
+    /// Day the item can be started on, it is hidden until then.
+    #[serde(default)]
+    pub start_date: Option<NaiveDate>,
+    /// Time on the item is tracked but left out of the billed time and cost.
+    #[serde(default)]
+    pub non_billable: bool,
+    /// Place in the manual order, kept while the list is sorted otherwise.
+    /// 0 until the item was placed.
+    #[serde(default)]
+    pub order: i64,
+    /// Code of the project in an outside tool like Jira or Toggl, taking
+    /// over the list's.
+    #[serde(default)]
+    pub project_code: Option<String>,
+    /// Tracked times that ring the bell once the duration steps over them.
+    #[serde(default)]
+    pub alarms: Vec<i64>,
+    /// Since when the item waits on something else, None while it is not
+    /// blocked.
+    #[serde(default)]
+    pub blocked_at: Option<DateTime<Local>>,
+    // TOML needs tables after plain values, so keep these last.
+    #[serde(default)]
+    pub subtasks: Vec<Subtask>,
+    // Empty arrays would be plain values after the tables above.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stops: Vec<Stop>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timers: Vec<SubTimer>,
+    /// Time the timers counted per day, for the breakdown by day.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub daily_log: BTreeMap<NaiveDate, i64>,
+    /// Free-form fields like a client or invoice number.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, String>,
+}
+
+/// Removes the last grapheme, so accented letters and emoji go in one keystroke.
+pub fn pop_grapheme(text: &mut String) {
+    if let Some((index, _)) = text.grapheme_indices(true).next_back() {
+        text.truncate(index);
+    }
+}
+
+/// Reads a color name like "lightblue" or a hex code like "#ff8800".
+fn parse_color(name: &str) -> Option<Color> {
+    let color = match name {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" => Color::Gray,
+        "darkgray" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => {
+            let hex = name.strip_prefix('#').filter(|hex| hex.len() == 6)?;
+            let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+            Color::Rgb(channel(0)?, channel(2)?, channel(4)?)
+        }
+    };
+    Some(color)
+}
+
+/// Shortens `text` to at most `width` columns, ending it with an ellipsis.
+/// Wide characters count as two columns.
+fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut truncated = String::new();
+    for grapheme in text.graphemes(true) {
+        if truncated.width() + grapheme.width() + 1 > width {
+            break;
+        }
+        truncated.push_str(grapheme);
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Centers a hint inside the borders of an empty pane.
+fn draw_empty_hint<B: Backend>(frame: &mut Frame<B>, area: Rect, hint: &str) {
+    let inner = area.inner(&Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    let height = inner.height.min(3);
+    let hint_area = Rect::new(
+        inner.x,
+        inner.y + (inner.height - height) / 2,
+        inner.width,
+        height,
+    );
+    let paragraph = Paragraph::new(Span::styled(hint, Style::default().fg(Color::Gray)))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, hint_area);
+}
+
+/// Shows how many rows of a bordered list are scrolled out of view, on its
+/// top and bottom border. Rows are one line each and the list state is new
+/// every frame, so the list only scrolls as far as the selected row needs.
+fn draw_scroll_hints<B: Backend>(
+    frame: &mut Frame<B>,
+    area: Rect,
+    rows: usize,
+    selected: Option<usize>,
+) {
+    let height = area.height.saturating_sub(2) as usize;
+    if height == 0 || rows <= height {
+        return;
+    }
+    let above = selected.map_or(0, |selected| (selected + 1).saturating_sub(height));
+    let below = rows.saturating_sub(above + height);
+    let width = area.width.saturating_sub(2);
+    for (count, arrow, y) in [(above, "▲", area.y), (below, "▼", area.y + area.height - 1)].iter()
+    {
+        if *count == 0 {
+            continue;
+        }
+        let hint = Paragraph::new(Span::styled(
+            format!(" {} {} more ", arrow, count),
+            Style::default().fg(Color::Gray),
+        ))
+        .alignment(Alignment::Right);
+        frame.render_widget(hint, Rect::new(area.x + 1, *y, width, 1));
+    }
+}
+
+/// Picks a stable chip color for a tag.
+pub fn tag_color(tag: &str) -> Color {
+    const COLORS: [Color; 6] = [
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+    ];
+    let hash = tag
+        .bytes()
+        .fold(0usize, |hash, byte| hash.wrapping_add(byte as usize));
+    COLORS[hash % COLORS.len()]
+}
+
+pub const DUE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Below this size only a hint to enlarge the terminal is drawn.
+const MIN_WIDTH: u16 = 20;
+const MIN_HEIGHT: u16 = 5;
+/// Lines the description gets in the dialog, on top of its label and first line.
+const DESC_LINES: u16 = 5;
+
+const PROGRESS_STEP: u8 = 10;
+/// Cells of the done / total bar after a list's name.
+const PROGRESS_BAR_WIDTH: usize = 8;
+/// Columns of the daily goal gauge in the status bar.
+const GOAL_WIDTH: u16 = 24;
+/// Cells of the estimate / actual bar on the card.
+const VARIANCE_BAR_WIDTH: i64 = 20;
+/// Places kept for going back with Left.
+const NAV_HISTORY_LIMIT: usize = 20;
+
+/// Tracked time against the estimate, scaled to the longer of the two, with
+/// "│" marking the estimate and the time over it in red.
+fn variance_bar(duration: i64, target: i64) -> Spans<'static> {
+    let scale = duration.max(target).max(1);
+    let cell = |millis: i64| (millis * VARIANCE_BAR_WIDTH / scale) as usize;
+    let (used, marker) = (
+        cell(duration),
+        cell(target).min(VARIANCE_BAR_WIDTH as usize - 1),
+    );
+    let width = VARIANCE_BAR_WIDTH as usize;
+
+    let mut spans = Vec::new();
+    for position in 0..width {
+        let span = if position == marker {
+            Span::styled("│", Style::default().fg(Color::White))
+        } else if position < used && position > marker {
+            Span::styled("█", Style::default().fg(Color::Red))
+        } else if position < used {
+            Span::styled("█", Style::default().fg(Color::Green))
+        } else {
+            Span::styled("·", Style::default().fg(Color::Gray))
+        };
+        spans.push(span);
+    }
+    let variance = duration - target;
+    spans.push(Span::raw(if variance > 0 {
+        format!(
+            " {} over",
+            format_duration(variance, DurationPrecision::Minutes)
+        )
+    } else {
+        format!(
+            " {} under",
+            format_duration(-variance, DurationPrecision::Minutes)
+        )
+    }));
+    Spans::from(spans)
+}
+
+/// A bar like "▓▓▓░░" with the done share filled, rounded to whole cells.
+fn progress_bar(done: usize, total: usize) -> String {
+    let filled = (done * PROGRESS_BAR_WIDTH + total / 2) / total.max(1);
+    format!(
+        "{}{}",
+        "▓".repeat(filled),
+        "░".repeat(PROGRESS_BAR_WIDTH - filled)
+    )
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LongRunning {
+    Warning,
+    Alert,
+}
+
+/// Formats a duration like "1h 2m 3s", rounded to the given precision.
+pub fn format_duration(millis: i64, precision: DurationPrecision) -> String {
+    let unit = precision.millis();
+    let mut output = String::new();
+    let mut duration = Duration::milliseconds((millis + unit / 2) / unit * unit);
+
+    if duration.num_weeks() > 0 {
+        output.push_str(format!("{}w ", duration.num_weeks()).as_str());
+        if let Some(dur) = duration.checked_sub(&Duration::weeks(duration.num_weeks())) {
+            duration = dur;
+        }
+    }
+
+    if precision == DurationPrecision::Hours {
+        output.push_str(format!("{}h", duration.num_hours()).as_str());
+        return output;
+    }
+
+    if duration.num_hours() > 0 {
+        output.push_str(format!("{}h ", duration.num_hours()).as_str());
+        if let Some(dur) = duration.checked_sub(&Duration::hours(duration.num_hours())) {
+            duration = dur;
+        }
+    }
+
+    if precision == DurationPrecision::Minutes {
+        output.push_str(format!("{}m", duration.num_minutes()).as_str());
+        return output;
+    }
+
+    if duration.num_minutes() > 0 {
+        output.push_str(format!("{}m ", duration.num_minutes()).as_str());
+        if let Some(dur) = duration.checked_sub(&Duration::minutes(duration.num_minutes())) {
+            duration = dur;
+        }
+    }
+
+    output.push_str(format!("{}s", duration.num_seconds()).as_str());
+    output
+}
+
+/// Reads durations like "1h30m", "90m" or "2h 15m"; a bare number is
+/// minutes. Returns `None` for anything else or a zero duration.
+pub fn parse_duration(text: &str) -> Option<i64> {
+    let text = text.trim().to_lowercase();
+    if let Ok(minutes) = text.parse::<i64>() {
+        return Some(Duration::minutes(minutes).num_milliseconds()).filter(|millis| *millis > 0);
+    }
+
+    let mut millis = 0;
+    let mut number = String::new();
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value = number.parse::<i64>().ok()?;
+        number.clear();
+        millis += match c {
+            'w' => Duration::weeks(value),
+            'd' => Duration::days(value),
+            'h' => Duration::hours(value),
+            'm' => Duration::minutes(value),
+            's' => Duration::seconds(value),
+            _ => return None,
+        }
+        .num_milliseconds();
+    }
+    Some(millis).filter(|millis| number.is_empty() && *millis > 0)
+}
+
+/// Rounds to the nearest multiple of `increment`, halves going up.
+pub fn round_to_nearest(millis: i64, increment: i64) -> i64 {
+    if increment <= 0 {
+        return millis;
+    }
+    (millis + increment / 2).div_euclid(increment) * increment
+}
+
+/// Decimal hours like "2.25h", without trailing zeros.
+pub fn format_hours(millis: i64) -> String {
+    let hours = format!(
+        "{:.2}",
+        millis as f64 / Duration::hours(1).num_milliseconds() as f64
+    );
+    format!("{}h", hours.trim_end_matches('0').trim_end_matches('.'))
+}
+
+/// Formats a duration the ISO 8601 way, e.g. `PT1H30M`, for other tools to parse.
+pub fn format_iso8601(millis: i64) -> String {
+    let seconds = millis.max(0) / 1000;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours == 0 && minutes == 0 && seconds == 0 {
+        return "PT0S".to_string();
+    }
+
+    let mut output = String::from("PT");
+    if hours > 0 {
+        output.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        output.push_str(&format!("{}M", minutes));
+    }
+    if seconds > 0 {
+        output.push_str(&format!("{}S", seconds));
+    }
+    output
+}
+
+impl<T> GroupList<T> {
+    pub fn new(name: String) -> GroupList<T> {
+        GroupList {
+            id: 0,
+            name,
+            sort_mode: SortMode::default(),
+            hourly_rate: None,
+            currency: String::new(),
+            timebox_minutes: None,
+            wip_limit: None,
+            color: None,
+            icon: None,
+            display_rounding: None,
+            project_code: None,
+            weekly_budget: None,
+            collapsed: false,
+            paused_ids: Vec::new(),
+            list: StatefulList::new(),
+        }
+    }
+}
+
+impl GroupList<Item> {
+    pub fn total_duration(&self) -> i64 {
+        self.list.items.iter().map(|item| item.duration).sum()
+    }
+
+    /// Tracked time of the items that are billed.
+    pub fn billable_duration(&self) -> i64 {
+        self.list
+            .items
+            .iter()
+            .filter(|item| item.billable())
+            .map(|item| item.duration)
+            .sum()
+    }
+
+    /// Done and total number of tasks, dividers don't count.
+    pub fn done_count(&self) -> (usize, usize) {
+        let tasks = self.list.items.iter().filter(|item| item.is_task());
+        let total = tasks.clone().count();
+        (tasks.filter(|item| item.done()).count(), total)
+    }
+
+    pub fn total_points(&self) -> u32 {
+        self.list.items.iter().filter_map(|item| item.points).sum()
+    }
+
+    pub fn billed_duration(&self, increment: Option<i64>) -> i64 {
+        self.list
+            .items
+            .iter()
+            .map(|item| item.billed_duration(increment))
+            .sum()
+    }
+
+    /// The item's own project code, or else the list's.
+    pub fn project_code_of<'a>(&'a self, item: &'a Item) -> Option<&'a str> {
+        item.project_code
+            .as_deref()
+            .or(self.project_code.as_deref())
+    }
+
+    /// Each item's time rounded to the nearest `display_rounding`, summed.
+    pub fn rounded_duration(&self) -> Option<i64> {
+        let increment = Duration::minutes(self.display_rounding?).num_milliseconds();
+        Some(
+            self.list
+                .items
+                .iter()
+                .map(|item| round_to_nearest(item.duration, increment))
+                .sum(),
+        )
+    }
+
+    /// Billed duration at the hourly rate, rounded to cents.
+    pub fn cost(&self, increment: Option<i64>) -> Option<f64> {
+        let hours =
+            self.billed_duration(increment) as f64 / Duration::hours(1).num_milliseconds() as f64;
+        self.hourly_rate
+            .map(|rate| (hours * rate * 100.0).round() / 100.0)
+    }
+
+    pub fn formatted_cost(&self, increment: Option<i64>) -> Option<String> {
+        self.cost(increment).map(|cost| {
+            format!("{:.2} {}", cost, self.currency)
+                .trim_end()
+                .to_string()
+        })
+    }
+
+    pub fn last_activity(&self) -> Option<DateTime<Local>> {
+        self.list.items.iter().filter_map(Item::last_activity).max()
+    }
+
+    /// Lists without activity for the configured number of days, 0 disables.
+    fn stale(&self, config: &Config) -> bool {
+        if config.stale_after_days <= 0 {
+            return false;
+        }
+        match self.last_activity() {
+            Some(last) => Local::now() - last > Duration::days(config.stale_after_days),
+            None => false,
+        }
+    }
+
+    pub fn today_duration(&self) -> i64 {
+        self.list.items.iter().map(Item::duration_today).sum()
+    }
+
+    /// Items that are started but not done yet.
+    pub fn in_progress(&self) -> usize {
+        self.list
+            .items
+            .iter()
+            .filter(|item| item.started() && !item.done())
+            .count()
+    }
+
+    pub fn over_wip_limit(&self) -> bool {
+        matches!(self.wip_limit, Some(limit) if self.in_progress() > limit)
+    }
+
+    /// Whether starting another item would exceed the WIP limit.
+    pub fn wip_full(&self) -> bool {
+        matches!(self.wip_limit, Some(limit) if self.in_progress() >= limit)
+    }
+
+    /// Reorders the items by the list's sort mode, keeping pinned items on top
+    /// and the selection on the same item.
+    pub fn apply_sort(&mut self) {
+        let selected = self
+            .list
+            .state
+            .selected()
+            .and_then(|index| self.list.items.get(index))
+            .map(|item| item.id);
+
+        let items = &mut self.list.items;
+        // Items added while sorted go after the others in the manual order.
+        let mut last = items.iter().map(|item| item.order).max().unwrap_or(0);
+        for item in items.iter_mut().filter(|item| item.order == 0) {
+            last += 1;
+            item.order = last;
+        }
+        // Ties go by id, so equal items keep one order from save to save.
+        match self.sort_mode {
+            SortMode::Manual => {}
+            SortMode::Title => items.sort_by_key(|item| (item.title.to_lowercase(), item.id)),
+            SortMode::Duration => {
+                items.sort_by_key(|item| (std::cmp::Reverse(item.duration), item.id))
+            }
+            SortMode::Due => {
+                items.sort_by_key(|item| (item.due_at.is_none(), item.due_at, item.id))
+            }
+            SortMode::Points => items
+                .sort_by_key(|item| (std::cmp::Reverse(item.points.unwrap_or_default()), item.id)),
+        }
+        items.sort_by_key(|item| !item.pinned);
+        // Unsorted, the list's order is the manual order.
+        if self.sort_mode == SortMode::Manual {
+            for (index, item) in items.iter_mut().enumerate() {
+                item.order = index as i64 + 1;
+            }
+        }
+
+        if let Some(id) = selected {
+            let index = items.iter().position(|item| item.id == id);
+            self.list.state.select(index);
+        }
+    }
 }
 
 impl Item {
-    pub fn formatted_duration(&self) -> String {
-        let mut output = "Duration:".to_string();
-        let mut duration = Duration::milliseconds(self.duration);
+    pub fn formatted_duration(&self, precision: DurationPrecision) -> String {
+        format!("Duration: {}", format_duration(self.duration, precision))
+    }
 
-        if duration.num_weeks() > 0 {
-            output.push_str(format!(" {}w", duration.num_weeks()).as_str());
-            if let Some(dur) = duration.checked_sub(&Duration::weeks(duration.num_weeks())) {
-                duration = dur;
+    /// Time left on a countdown, negative once it ran out.
+    fn remaining(&self) -> Option<i64> {
+        match self.mode {
+            TimerMode::CountDown => self.target.map(|target| target - self.duration),
+            TimerMode::CountUp => None,
+        }
+    }
+
+    /// A countdown shows how much of its target is used up, full and red
+    /// once in overtime, other items their manual progress.
+    fn gauge(&self) -> Option<(u16, Color)> {
+        match (self.remaining(), self.target) {
+            (Some(_), Some(target)) if self.overtime().is_some() || target <= 0 => {
+                Some((100, Color::Red))
             }
+            (Some(_), Some(target)) => Some((
+                (self.duration * 100 / target).clamp(0, 100) as u16,
+                Color::Cyan,
+            )),
+            _ if self.progress > 0 => Some((self.progress.into(), Color::Green)),
+            _ => None,
+        }
+    }
+
+    /// Time a countdown ran past its target. Reaching the target exactly is
+    /// not overtime yet.
+    fn overtime(&self) -> Option<i64> {
+        self.remaining()
+            .filter(|remaining| *remaining < 0)
+            .map(|remaining| -remaining)
+    }
+
+    fn track_today(&mut self, millis: i64) {
+        let today = Local::today().naive_local();
+        if self.tracked_on != Some(today) {
+            self.tracked_on = Some(today);
+            self.tracked_today = 0;
         }
+        self.tracked_today += millis;
+        *self.daily_log.entry(today).or_insert(0) += millis;
+    }
 
-        if duration.num_hours() > 0 {
-            output.push_str(format!(" {}h", duration.num_hours()).as_str());
-            if let Some(dur) = duration.checked_sub(&Duration::hours(duration.num_hours())) {
-                duration = dur;
+    /// The duration rounded up to the next billing increment in minutes.
+    pub fn billed_duration(&self, increment: Option<i64>) -> i64 {
+        if !self.billable() {
+            return 0;
+        }
+        match increment.filter(|minutes| *minutes > 0) {
+            Some(minutes) => {
+                let increment = Duration::minutes(minutes).num_milliseconds();
+                (self.duration + increment - 1) / increment * increment
             }
+            None => self.duration,
+        }
+    }
+
+    /// Average tracked time per day since the item was started, counting
+    /// at least one day.
+    fn daily_pace(&self) -> Option<i64> {
+        let start_at = self.start_at?;
+        if self.duration <= 0 {
+            return None;
+        }
+        let days = (Local::now() - start_at).num_days().max(0) + 1;
+        Some(self.duration / days)
+    }
+
+    /// Days until the target is reached at the current pace.
+    fn days_to_target(&self) -> Option<i64> {
+        let remaining = self.target? - self.duration;
+        let pace = self.daily_pace()?;
+        Some((remaining + pace - 1) / pace)
+    }
+
+    /// Most recent start or end of the item, now while it is running.
+    pub fn last_activity(&self) -> Option<DateTime<Local>> {
+        if self.running() {
+            return Some(Local::now());
+        }
+        let last_stop = self.stops.iter().map(|stop| stop.at).max();
+        self.start_at.max(self.end_at).max(last_stop)
+    }
+
+    /// How long the item has not been worked on, like "idle 2d".
+    fn idle_label(&self, now: DateTime<Local>) -> String {
+        if self.running() {
+            return "active".to_string();
+        }
+        let idle = match self.last_activity() {
+            Some(last) => now.signed_duration_since(last),
+            None => return "never started".to_string(),
+        };
+        if idle.num_days() > 0 {
+            format!("idle {}d", idle.num_days())
+        } else if idle.num_hours() > 0 {
+            format!("idle {}h", idle.num_hours())
+        } else {
+            format!("idle {}m", idle.num_minutes())
         }
+    }
+
+    /// How long the item has been blocked, like "blocked 3d".
+    fn blocked_label(&self, now: DateTime<Local>) -> Option<String> {
+        let waiting = now.signed_duration_since(self.blocked_at?);
+        Some(if waiting.num_days() > 0 {
+            format!("blocked {}d", waiting.num_days())
+        } else if waiting.num_hours() > 0 {
+            format!("blocked {}h", waiting.num_hours())
+        } else {
+            format!("blocked {}m", waiting.num_minutes())
+        })
+    }
 
-        if duration.num_minutes() > 0 {
-            output.push_str(format!(" {}m", duration.num_minutes()).as_str());
-            if let Some(dur) = duration.checked_sub(&Duration::minutes(duration.num_minutes())) {
-                duration = dur;
+    /// Blocked for longer than the configured warning, 0 disables.
+    fn blocked_too_long(&self, config: &Config, now: DateTime<Local>) -> bool {
+        match self.blocked_at {
+            Some(at) if config.blocked_warning_days > 0 => {
+                now - at > Duration::days(config.blocked_warning_days)
             }
+            _ => false,
         }
+    }
+
+    pub fn focused_today(&self) -> bool {
+        self.focus_date == Some(Local::today().naive_local())
+    }
+
+    /// Whether the item can only be started on a later day.
+    pub fn scheduled(&self) -> bool {
+        matches!(self.start_date, Some(date) if date > Local::today().naive_local())
+    }
+
+    pub fn duration_today(&self) -> i64 {
+        if self.tracked_on == Some(Local::today().naive_local()) {
+            self.tracked_today
+        } else {
+            0
+        }
+    }
+
+    pub fn billable(&self) -> bool {
+        !self.non_billable
+    }
+
+    pub fn is_task(&self) -> bool {
+        self.kind == ItemKind::Task
+    }
+
+    fn subtasks_done(&self) -> usize {
+        self.subtasks.iter().filter(|subtask| subtask.done).count()
+    }
+
+    fn set_subtasks_done(&mut self, done: bool) {
+        for subtask in &mut self.subtasks {
+            subtask.done = done;
+        }
+    }
+
+    /// Marks the item done once all subtasks are, and reopens it when one
+    /// of them is open again.
+    pub fn sync_done_with_subtasks(&mut self) {
+        if self.subtasks.is_empty() {
+            return;
+        }
+        let all_done = self.subtasks_done() == self.subtasks.len();
+        if all_done && !self.done() {
+            self.end_at = Some(Local::now());
+        } else if !all_done && self.done() {
+            self.end_at = None;
+        }
+    }
+
+    fn long_running(&self, config: &Config) -> Option<LongRunning> {
+        let exceeds = |minutes: i64| {
+            minutes > 0 && self.duration > Duration::minutes(minutes).num_milliseconds()
+        };
+        if !self.running() {
+            None
+        } else if exceeds(config.long_running_alert_minutes) {
+            Some(LongRunning::Alert)
+        } else if exceeds(config.long_running_warning_minutes) {
+            Some(LongRunning::Warning)
+        } else {
+            None
+        }
+    }
+
+    /// Starts the timer, or resumes it if it was paused. Repeating it changes
+    /// nothing, and a done item stays done.
+    pub fn start(&mut self) {
+        if self.start_at.is_none() {
+            self.start_at = Some(Local::now());
+        }
+        self.paused = false;
+    }
 
-        output.push_str(format!(" {}s", duration.num_seconds()).as_str());
-        output
+    /// Pauses or resumes the timer. Only a started item that is not done can
+    /// be paused, so no item waits paused before it ever ran.
+    pub fn toggle_pause(&mut self) {
+        if self.started() && !self.done() {
+            self.paused = !self.paused;
+        }
+    }
+
+    /// Puts the item back to not started with no time tracked.
+    pub fn reset(&mut self) {
+        self.start_at = None;
+        self.end_at = None;
+        self.paused = false;
+        self.duration = 0;
+        self.tracked_today = 0;
+        self.daily_log.clear();
+        self.over_estimate = false;
+        for timer in &mut self.timers {
+            timer.duration = 0;
+            timer.running = false;
+        }
+    }
+
+    pub fn running(&self) -> bool {
+        self.is_task() && self.started() && !self.done() && !self.paused
+    }
+
+    /// Whether ticks add to the item's time, which paused items only do
+    /// when `count_paused` is set.
+    pub fn counting(&self, count_paused: bool) -> bool {
+        self.running() || (count_paused && self.is_task() && self.started() && !self.done())
     }
 
-    fn started(&self) -> bool {
+    pub fn started(&self) -> bool {
         self.start_at.is_some()
     }
 
-    fn done(&self) -> bool {
+    pub fn done(&self) -> bool {
         self.end_at.is_some()
     }
+
+    fn over_target(&self) -> bool {
+        matches!(self.target, Some(target) if self.duration >= target)
+    }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy, PartialEq, Default)]
 pub enum Input {
+    #[default]
     Titel,
+    Target,
+    Alarms,
+    Points,
+    Due,
+    Start,
+    Tags,
+    Link,
+    Code,
     Desc,
+    /// Display rounding and weekly budget in the list settings.
+    Rounding,
+    Budget,
 }
 
-impl Default for Input {
-    fn default() -> Self {
-        Input::Titel
+impl Input {
+    fn next(self) -> Input {
+        match self {
+            Input::Titel => Input::Target,
+            Input::Target => Input::Alarms,
+            Input::Alarms => Input::Points,
+            Input::Points => Input::Due,
+            Input::Due => Input::Start,
+            Input::Start => Input::Tags,
+            Input::Tags => Input::Link,
+            Input::Link => Input::Code,
+            Input::Code => Input::Desc,
+            Input::Desc | Input::Rounding | Input::Budget => Input::Titel,
+        }
     }
 }
 #[derive(Clone)]
 pub enum DialogState {
     New,
     Edit,
+    Replace,
+    Subtask,
+    Meta,
+    Split,
+    /// A note for the item that was just marked done.
+    StopNote {
+        list_index: usize,
+        id: u64,
+    },
+    ListSettings,
+    /// A new item for the inbox list, from anywhere.
+    Capture,
+    /// A named timer for the selected item.
+    Timer,
     Hide,
 }
 #[derive(Clone)]
 pub struct Dialog {
     pub input: Item,
+    pub target: String,
+    pub due: String,
+    pub start: String,
+    pub tags: String,
+    pub link: String,
+    pub points: String,
+    pub code: String,
+    pub alarms: String,
+    pub rounding: String,
+    pub budget: String,
+    pub case_sensitive: bool,
     pub selected_input: Input,
     pub state: DialogState,
 }
@@ -95,46 +1093,238 @@ impl Default for Dialog {
     fn default() -> Self {
         Dialog {
             input: Item::default(),
+            target: String::new(),
+            due: String::new(),
+            start: String::new(),
+            tags: String::new(),
+            link: String::new(),
+            points: String::new(),
+            code: String::new(),
+            alarms: String::new(),
+            rounding: String::new(),
+            budget: String::new(),
+            case_sensitive: true,
             selected_input: Input::Titel,
             state: DialogState::Hide,
         }
     }
 }
 
-impl<'a> Dialog {
+impl Dialog {
     pub fn process_input(&mut self, key: KeyCode, modi: KeyModifiers) {
         match (key, modi) {
             (KeyCode::Esc, _) => {
                 self.close_dialog();
             }
-            (KeyCode::Tab, _) => match self.selected_input {
-                Input::Titel => self.selected_input = Input::Desc,
-                Input::Desc => self.selected_input = Input::Titel,
-            },
+            (KeyCode::Tab, _) => {
+                self.selected_input = match (&self.state, self.selected_input) {
+                    (DialogState::Replace, Input::Titel) | (DialogState::Meta, Input::Titel) => {
+                        Input::Desc
+                    }
+                    (DialogState::Replace, _) | (DialogState::Meta, _) => Input::Titel,
+                    (DialogState::ListSettings, Input::Titel) => Input::Desc,
+                    (DialogState::ListSettings, Input::Desc) => Input::Target,
+                    (DialogState::ListSettings, Input::Target) => Input::Points,
+                    (DialogState::ListSettings, Input::Points) => Input::Tags,
+                    (DialogState::ListSettings, Input::Tags) => Input::Link,
+                    (DialogState::ListSettings, Input::Link) => Input::Rounding,
+                    (DialogState::ListSettings, Input::Rounding) => Input::Budget,
+                    (DialogState::ListSettings, Input::Budget) => Input::Code,
+                    (DialogState::ListSettings, _) => Input::Titel,
+                    (DialogState::Split, Input::Titel) => Input::Target,
+                    (DialogState::Split, _) => Input::Titel,
+                    (DialogState::StopNote { .. }, _) => Input::Titel,
+                    (DialogState::Timer, _) => Input::Titel,
+                    (_, input) => input.next(),
+                }
+            }
+            (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
+                self.case_sensitive = !self.case_sensitive;
+            }
+            (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
+                self.input.auto_stop_at_target = !self.input.auto_stop_at_target;
+            }
+            (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
+                self.input.mode = match self.input.mode {
+                    TimerMode::CountUp => TimerMode::CountDown,
+                    TimerMode::CountDown => TimerMode::CountUp,
+                };
+            }
+            (KeyCode::Char('v'), KeyModifiers::CONTROL) => {
+                self.input.kind = match self.input.kind {
+                    ItemKind::Task => ItemKind::Divider,
+                    ItemKind::Divider => ItemKind::Task,
+                };
+            }
             (KeyCode::Char(x), _) => match self.selected_input {
                 Input::Titel => self.input.title.push(x),
+                Input::Target => self.target.push(x),
+                Input::Alarms => self.alarms.push(x),
+                Input::Due => self.due.push(x),
+                Input::Start => self.start.push(x),
+                Input::Tags => self.tags.push(x),
+                Input::Link => self.link.push(x),
+                Input::Code => self.code.push(x),
+                Input::Rounding => {
+                    if x.is_ascii_digit() {
+                        self.rounding.push(x)
+                    }
+                }
+                Input::Budget => self.budget.push(x),
+                Input::Points => {
+                    if x.is_ascii_digit() {
+                        self.points.push(x)
+                    }
+                }
                 Input::Desc => self.input.desc.push(x),
             },
             (KeyCode::Backspace, _) => {
                 match self.selected_input {
                     Input::Titel => {
-                        self.input.title.pop();
+                        pop_grapheme(&mut self.input.title);
                     }
-                    Input::Desc => {
-                        self.input.desc.pop();
+                    Input::Target => {
+                        pop_grapheme(&mut self.target);
                     }
-                };
-            }
-            _ => {}
-        }
+                    Input::Alarms => {
+                        pop_grapheme(&mut self.alarms);
+                    }
+                    Input::Due => {
+                        pop_grapheme(&mut self.due);
+                    }
+                    Input::Start => {
+                        pop_grapheme(&mut self.start);
+                    }
+                    Input::Tags => {
+                        pop_grapheme(&mut self.tags);
+                    }
+                    Input::Link => {
+                        pop_grapheme(&mut self.link);
+                    }
+                    Input::Code => {
+                        pop_grapheme(&mut self.code);
+                    }
+                    Input::Rounding => {
+                        pop_grapheme(&mut self.rounding);
+                    }
+                    Input::Budget => {
+                        pop_grapheme(&mut self.budget);
+                    }
+                    Input::Points => {
+                        pop_grapheme(&mut self.points);
+                    }
+                    Input::Desc => {
+                        pop_grapheme(&mut self.input.desc);
+                    }
+                };
+            }
+            _ => {}
+        }
     }
 
     pub fn close_dialog(&mut self) {
         self.state = DialogState::Hide;
         self.input = Item::default();
+        self.target = String::new();
+        self.alarms = String::new();
+        self.rounding = String::new();
+        self.budget = String::new();
+        self.due = String::new();
+        self.start = String::new();
+        self.tags = String::new();
+        self.link = String::new();
+        self.code = String::new();
+        self.points = String::new();
+        self.case_sensitive = true;
         self.selected_input = Input::Titel;
     }
 
+    pub fn edit(&mut self, item: Item) {
+        self.alarms = item
+            .alarms
+            .iter()
+            .map(|alarm| format_duration(*alarm, DurationPrecision::Minutes))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.target = item
+            .target
+            .map(|target| format_duration(target, DurationPrecision::Minutes))
+            .unwrap_or_default();
+        self.due = item
+            .due_at
+            .map(|due_at| due_at.format(DUE_FORMAT).to_string())
+            .unwrap_or_default();
+        self.start = item
+            .start_date
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        self.tags = item.tags.join(", ");
+        self.link = item.link.clone().unwrap_or_default();
+        self.code = item.project_code.clone().unwrap_or_default();
+        self.points = item
+            .points
+            .map(|points| points.to_string())
+            .unwrap_or_default();
+        self.input = item;
+        self.display(DialogState::Edit);
+    }
+
+    /// Accepts either a full `YYYY-MM-DD HH:MM` timestamp, a bare date,
+    /// which is due at the end of that day, or a phrase like "tomorrow".
+    fn due_at(&self, week_start: Weekday) -> Option<DateTime<Local>> {
+        let due = self.due.trim();
+        let naive = NaiveDateTime::parse_from_str(due, DUE_FORMAT).or_else(|_| {
+            NaiveDate::parse_from_str(due, "%Y-%m-%d").map(|date| date.and_hms(23, 59, 59))
+        });
+        match naive {
+            Ok(naive) => Local.from_local_datetime(&naive).single(),
+            Err(_) => parse_relative(due, Local::now(), week_start),
+        }
+    }
+
+    /// Accepts a `YYYY-MM-DD` date or a phrase like "monday".
+    fn start_date(&self, week_start: Weekday) -> Option<NaiveDate> {
+        let start = self.start.trim();
+        NaiveDate::parse_from_str(start, "%Y-%m-%d")
+            .ok()
+            .or_else(|| {
+                parse_relative(start, Local::now(), week_start).map(|at| at.date().naive_local())
+            })
+    }
+
+    fn tag_list(&self) -> Vec<String> {
+        self.tags
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+
+    fn link(&self) -> Option<String> {
+        Some(self.link.trim().to_string()).filter(|link| !link.is_empty())
+    }
+
+    fn code(&self) -> Option<String> {
+        Some(self.code.trim().to_string()).filter(|code| !code.is_empty())
+    }
+
+    fn target_millis(&self) -> Option<i64> {
+        parse_duration(&self.target)
+    }
+
+    /// The comma separated alarm times, sorted, `None` if one can't be read.
+    fn alarm_list(&self) -> Option<Vec<i64>> {
+        let mut alarms = self
+            .alarms
+            .split(',')
+            .filter(|alarm| !alarm.trim().is_empty())
+            .map(parse_duration)
+            .collect::<Option<Vec<_>>>()?;
+        alarms.sort_unstable();
+        alarms.dedup();
+        Some(alarms)
+    }
+
     pub fn displayed(&self) -> bool {
         !matches!(self.state, DialogState::Hide)
     }
@@ -146,41 +1336,1687 @@ impl<'a> Dialog {
     pub fn editing(&self) -> bool {
         matches!(self.state, DialogState::Edit)
     }
+
+    pub fn replacing(&self) -> bool {
+        matches!(self.state, DialogState::Replace)
+    }
+
+    pub fn adding_subtask(&self) -> bool {
+        matches!(self.state, DialogState::Subtask)
+    }
+
+    pub fn adding_timer(&self) -> bool {
+        matches!(self.state, DialogState::Timer)
+    }
+
+    pub fn stop_note(&self) -> Option<(usize, u64)> {
+        match self.state {
+            DialogState::StopNote { list_index, id } => Some((list_index, id)),
+            _ => None,
+        }
+    }
+
+    pub fn splitting(&self) -> bool {
+        matches!(self.state, DialogState::Split)
+    }
+
+    /// Whether a metadata field is edited, with the key in the title and
+    /// the value in the description.
+    pub fn editing_meta(&self) -> bool {
+        matches!(self.state, DialogState::Meta)
+    }
+
+    pub fn capturing(&self) -> bool {
+        matches!(self.state, DialogState::Capture)
+    }
+
+    pub fn editing_list(&self) -> bool {
+        matches!(self.state, DialogState::ListSettings)
+    }
+
+    /// Opens the list settings, using the title for the hourly rate, the
+    /// description for the currency, the target for the timebox, the
+    /// points for the WIP limit, the tags for the color and the link for
+    /// the icon.
+    pub fn edit_list(&mut self, list: &GroupList<Item>) {
+        self.input.title = list
+            .hourly_rate
+            .map(|rate| rate.to_string())
+            .unwrap_or_default();
+        self.input.desc = list.currency.clone();
+        self.target = list
+            .timebox_minutes
+            .map(|minutes| minutes.to_string())
+            .unwrap_or_default();
+        self.points = list
+            .wip_limit
+            .map(|limit| limit.to_string())
+            .unwrap_or_default();
+        self.tags = list.color.clone().unwrap_or_default();
+        self.link = list.icon.clone().unwrap_or_default();
+        self.code = list.project_code.clone().unwrap_or_default();
+        self.rounding = list
+            .display_rounding
+            .map(|minutes| minutes.to_string())
+            .unwrap_or_default();
+        self.budget = list
+            .weekly_budget
+            .map(|minutes| format_hours(Duration::minutes(minutes).num_milliseconds()))
+            .unwrap_or_default();
+        self.display(DialogState::ListSettings);
+    }
+
+    /// Whether the dialog shows the full set of item fields.
+    fn item_input(&self) -> bool {
+        matches!(
+            self.state,
+            DialogState::New | DialogState::Edit | DialogState::Capture
+        )
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct Filter {
+    pub hide_done: bool,
+    pub show_scheduled: bool,
+}
+
+impl Filter {
+    pub fn matches(&self, item: &Item) -> bool {
+        !(self.hide_done && item.done()) && (self.show_scheduled || !item.scheduled())
+    }
+}
+
+/// A list or item taken out with `x`, remembering where it came from so
+/// undo can put it back.
+pub enum Cut {
+    Group {
+        index: usize,
+        list: GroupList<Item>,
+    },
+    Item {
+        list_index: usize,
+        index: usize,
+        item: Item,
+    },
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct App {
     pub name: String,
-    pub group_list: StatefulList<GroupList<Item>>,
-    #[serde(skip)]
+    #[serde(default)]
+    pub next_id: u64,
+    #[serde(default)]
+    pub seen_onboarding: bool,
+    /// Stops all timers from counting, without touching their own state.
+    #[serde(default)]
+    pub all_paused: bool,
+    #[serde(default, rename = "active_group_index")]
     pub active_list: Option<usize>,
+    #[serde(default)]
+    pub card_layout: CardLayout,
+    /// Free text not tied to any list, for notes during timed work.
+    #[serde(default)]
+    pub scratchpad: String,
+    pub group_list: StatefulList<GroupList<Item>>,
+    #[serde(default)]
+    pub config: Config,
+    /// Time tracked per day, for the activity heatmap.
+    #[serde(default)]
+    pub daily_log: BTreeMap<NaiveDate, i64>,
+    // An empty array would be a plain value after the tables above.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bin: Vec<Deleted>,
+    /// Undo history kept across restarts, see `persist_undo`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<Operation>,
     #[serde(skip)]
     pub dialog: Dialog,
+    #[serde(skip)]
+    pub rename: Option<String>,
+    /// List whose items are merged into the next picked list.
+    #[serde(skip)]
+    pub merge_source: Option<usize>,
+    #[serde(skip)]
+    pub cut: Option<Cut>,
+    #[serde(skip)]
+    pub filter: Filter,
+    #[serde(skip)]
+    pub undo: Vec<Operation>,
+    #[serde(skip)]
+    pub redo: Vec<Operation>,
+    #[serde(skip)]
+    pub show_help: bool,
+    /// How many lines the help is scrolled down.
+    #[serde(skip)]
+    pub help_scroll: u16,
+    /// Shows the items as to-do, in progress and done columns.
+    #[serde(skip)]
+    pub board: bool,
+    #[serde(skip)]
+    pub group_by: GroupBy,
+    #[serde(skip)]
+    pub row_time_mode: RowTimeMode,
+    /// Gives the active list the whole screen.
+    #[serde(skip)]
+    pub zoomed: bool,
+    /// Hides the detail pane while no list is open, see `LeftAction`.
+    #[serde(skip)]
+    pub detail_hidden: bool,
+    /// Places left by jumps and list switches as the open list, the
+    /// selected list and its selected item, newest last.
+    #[serde(skip)]
+    pub nav_history: Vec<(Option<usize>, Option<usize>, Option<usize>)>,
+    /// Shows only the running item and its time while a timer runs.
+    #[serde(skip)]
+    pub minimal: bool,
+    #[serde(skip)]
+    pub timebox: Option<Timebox>,
+    #[serde(skip)]
+    pub frozen: Option<Frozen>,
+    /// Selected row while the recycle bin is shown.
+    #[serde(skip)]
+    pub bin_view: Option<usize>,
+    /// The cross-list panel shown and its selected row.
+    #[serde(skip)]
+    pub overview: Option<(Overview, usize)>,
+    #[serde(skip)]
+    pub import: Option<ImportPreview>,
+    #[serde(skip)]
+    pub csv_import: Option<CsvPreview>,
+    #[serde(skip)]
+    pub tag_view: Option<TagView>,
+    #[serde(skip)]
+    pub scratchpad_view: Option<ScratchpadView>,
+    /// The selected subtask while the subtasks of the selected item have
+    /// the focus instead of the item list.
+    #[serde(skip)]
+    pub subtask_focus: Option<usize>,
+    /// Selected timer of the selected item while they have the focus.
+    #[serde(skip)]
+    pub timer_focus: Option<usize>,
+    #[serde(skip)]
+    pub review: Option<Review>,
+    /// What was fixed while loading, shown until a key is pressed.
+    #[serde(skip)]
+    pub repairs: Vec<String>,
+    #[serde(skip)]
+    pub show_heatmap: bool,
+    #[serde(skip)]
+    pub as_of: Option<AsOfView>,
+    #[serde(skip)]
+    pub show_planning: bool,
+    /// Set when no database existed yet, until the first key press.
+    #[serde(skip)]
+    pub show_onboarding: bool,
+    #[serde(skip)]
+    pub confirm: Option<Confirm>,
+    /// Recent messages with when they were shown, the latest last.
+    #[serde(skip)]
+    pub status: VecDeque<(Instant, String)>,
+    #[serde(skip)]
+    pub show_status_history: bool,
+    #[serde(skip)]
+    pub last_saved: Option<DateTime<Local>>,
+    #[serde(skip)]
+    pub activity: Activity,
+    #[serde(skip)]
+    pub last_input: Option<DateTime<Local>>,
+    /// Whether the bell already rang for the current idle stretch.
+    #[serde(skip)]
+    pub idle_alerted: bool,
+    /// Per item id, how many reminder intervals its timer had passed.
+    #[serde(skip)]
+    pub reminded: HashMap<u64, i64>,
+    /// Per item id, where an item was before it sank as done.
+    #[serde(skip)]
+    pub sunk: HashMap<u64, usize>,
+    /// Whether a reminder is due that has not rung yet.
+    #[serde(skip)]
+    pub reminder_due: bool,
+    /// File the app was loaded from and is saved to.
+    #[serde(skip)]
+    pub path: String,
 }
 
-impl<'a> App {
+impl App {
     pub fn new(name: String) -> App {
         App {
             name,
-            group_list: StatefulList::new(),
+            next_id: 0,
+            seen_onboarding: false,
+            all_paused: false,
             active_list: None,
+            card_layout: CardLayout::default(),
+            scratchpad: String::new(),
+            group_list: StatefulList::new(),
+            config: Config::default(),
+            daily_log: BTreeMap::new(),
+            bin: Vec::new(),
+            history: Vec::new(),
             dialog: Dialog::default(),
+            rename: None,
+            merge_source: None,
+            cut: None,
+            filter: Filter::default(),
+            undo: Vec::new(),
+            redo: Vec::new(),
+            show_help: false,
+            help_scroll: 0,
+            board: false,
+            group_by: GroupBy::None,
+            row_time_mode: RowTimeMode::Elapsed,
+            zoomed: false,
+            detail_hidden: false,
+            nav_history: Vec::new(),
+            minimal: false,
+            timebox: None,
+            frozen: None,
+            bin_view: None,
+            overview: None,
+            import: None,
+            csv_import: None,
+            tag_view: None,
+            scratchpad_view: None,
+            subtask_focus: None,
+            timer_focus: None,
+            review: None,
+            repairs: Vec::new(),
+            show_heatmap: false,
+            as_of: None,
+            show_planning: false,
+            show_onboarding: false,
+            confirm: None,
+            status: VecDeque::new(),
+            show_status_history: false,
+            last_saved: None,
+            activity: Activity::default(),
+            last_input: None,
+            idle_alerted: false,
+            reminded: HashMap::new(),
+            sunk: HashMap::new(),
+            reminder_due: false,
+            path: DB_PATH.to_string(),
+        }
+    }
+
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        match self.config.toml_format {
+            TomlFormat::Compact => toml::to_string(self),
+            TomlFormat::Pretty => toml::to_string_pretty(self),
+        }
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.last_saved = Some(Local::now());
+    }
+
+    fn saved_ago(&self) -> String {
+        match self.last_saved {
+            Some(saved) => {
+                let ago = Local::now().signed_duration_since(saved);
+                if ago.num_hours() > 0 {
+                    format!("Saved {}h ago", ago.num_hours())
+                } else if ago.num_minutes() > 0 {
+                    format!("Saved {}m ago", ago.num_minutes())
+                } else {
+                    "Saved just now".to_string()
+                }
+            }
+            None => "Not saved yet".to_string(),
+        }
+    }
+
+    pub fn new_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    /// A new empty list with an id of its own.
+    pub fn new_list(&mut self, name: String) -> GroupList<Item> {
+        let mut list = GroupList::new(name);
+        list.id = self.new_id();
+        list
+    }
+
+    pub fn assign_missing_ids(&mut self) {
+        let mut next_id = self.next_id;
+        for list in &mut self.group_list.items {
+            if list.id == 0 {
+                next_id += 1;
+                list.id = next_id;
+            }
+            for item in &mut list.list.items {
+                if item.id == 0 {
+                    next_id += 1;
+                    item.id = next_id;
+                }
+            }
+        }
+        self.next_id = next_id;
+    }
+
+    pub fn sort_lists(&mut self) {
+        for list in &mut self.group_list.items {
+            list.apply_sort();
+        }
+    }
+
+    /// Opens the list called `name`, ignoring case, or stays on the lists
+    /// with a notice if there is none.
+    pub fn open_list_named(&mut self, name: &str) {
+        let index = self
+            .group_list
+            .items
+            .iter()
+            .position(|list| list.name.to_lowercase() == name.to_lowercase());
+        match index {
+            Some(index) => {
+                self.remember_location();
+                self.group_list.state.select(Some(index));
+                self.active_list = Some(index);
+                self.sort_active_list();
+            }
+            None => {
+                self.active_list = None;
+                self.notify(format!("No list called \"{}\"", name));
+            }
+        }
+    }
+
+    fn enter_list(&mut self) {
+        if self.active_list.is_none() {
+            self.active_list = self.group_list.state.selected();
+            self.sort_active_list();
+        }
+    }
+
+    /// Opens the previous or next list without leaving the item level. The
+    /// list keeps its last selection or selects its first visible item.
+    fn switch_list(&mut self, forward: bool) {
+        let current = match self.active_list {
+            Some(current) => current,
+            None => return,
+        };
+        let len = self.group_list.items.len();
+        let wrap = self.config.wrap_navigation;
+        let next = match forward {
+            true if current + 1 < len => current + 1,
+            true if wrap => 0,
+            false if current > 0 => current - 1,
+            false if wrap => len - 1,
+            _ => return,
+        };
+
+        self.remember_location();
+        self.active_list = Some(next);
+        self.group_list.state.select(Some(next));
+        self.sort_active_list();
+        let filter = self.filter;
+        let list = &mut self.group_list.items[next].list;
+        list.clamp_selection();
+        if list.state.selected().is_none() {
+            list.next_matching(false, |item| filter.matches(item));
+        }
+    }
+
+    /// Records the current place before a jump so Left can return to it.
+    fn remember_location(&mut self) {
+        let item = self
+            .active_list
+            .and_then(|index| self.group_list.items.get(index))
+            .and_then(|list| list.list.state.selected());
+        let location = (self.active_list, self.group_list.state.selected(), item);
+        if self.nav_history.last() == Some(&location) {
+            return;
+        }
+        self.nav_history.push(location);
+        if self.nav_history.len() > NAV_HISTORY_LIMIT {
+            self.nav_history.remove(0);
+        }
+    }
+
+    /// Returns to the last remembered place, skipping lists that are gone.
+    fn go_back(&mut self) {
+        let len = self.group_list.items.len();
+        while let Some((active, selected, item)) = self.nav_history.pop() {
+            if matches!(active.or(selected), Some(index) if index >= len) {
+                continue;
+            }
+            if let Some(index) = self.active_list {
+                self.group_list.items[index].list.state.select(None);
+            }
+            self.active_list = active;
+            self.group_list.state.select(selected);
+            if let Some(index) = active {
+                self.sort_active_list();
+                let list = &mut self.group_list.items[index].list;
+                list.state.select(item);
+                list.clamp_selection();
+            }
+            return;
+        }
+        self.notify("Nowhere to go back to");
+    }
+
+    pub fn sort_active_list(&mut self) {
+        if let Some(list) = self
+            .active_list
+            .and_then(|index| self.group_list.items.get_mut(index))
+        {
+            list.apply_sort();
+        }
+    }
+
+    fn cycle_sort_mode(&mut self) {
+        let index = self
+            .active_list
+            .or_else(|| self.group_list.state.selected());
+        if let Some(list) = index.and_then(|index| self.group_list.items.get_mut(index)) {
+            list.apply_sort();
+            list.sort_mode = list.sort_mode.next();
+            if list.sort_mode == SortMode::Manual {
+                list.list.items.sort_by_key(|item| (item.order, item.id));
+            }
+            list.apply_sort();
+        }
+    }
+
+    /// Keeps restored selections within the loaded lists.
+    pub fn clamp_selection(&mut self) {
+        self.group_list.clamp_selection();
+        for list in &mut self.group_list.items {
+            list.list.clamp_selection();
+        }
+        if let Some(index) = self.active_list {
+            if index < self.group_list.items.len() {
+                self.group_list.state.select(Some(index));
+            } else {
+                self.active_list = None;
+            }
+        }
+    }
+
+    /// Keeps the selections valid and visible after the terminal was resized.
+    pub fn resize(&mut self) {
+        self.clamp_selection();
+        self.group_list.reset_offset();
+        for list in &mut self.group_list.items {
+            list.list.reset_offset();
+        }
+    }
+
+    /// Points the active list's selection at the item with the given id, or at
+    /// the nearest item that passes the filter if it is hidden.
+    fn reselect(&mut self, id: u64) {
+        let filter = self.filter;
+        if let Some(index) = self.active_list {
+            if let Some(group_list) = self.group_list.items.get_mut(index) {
+                let list = &mut group_list.list;
+                if let Some(position) = list.items.iter().position(|item| item.id == id) {
+                    let nearest = (0..list.items.len())
+                        .filter(|index| filter.matches(&list.items[*index]))
+                        .min_by_key(|index| (*index as isize - position as isize).abs());
+                    list.state.select(nearest);
+                }
+            }
+        }
+    }
+
+    /// Selects the item at the given position, entering its list.
+    pub fn focus_item(&mut self, list_index: usize, index: usize) {
+        self.remember_location();
+        if let Some(active) = self.active_list.filter(|active| *active != list_index) {
+            if let Some(list) = self.group_list.items.get_mut(active) {
+                list.list.state.select(None);
+            }
+        }
+        self.active_list = Some(list_index);
+        self.group_list.state.select(Some(list_index));
+        if let Some(list) = self.group_list.items.get_mut(list_index) {
+            list.list.state.select(Some(index));
+        }
+    }
+
+    /// Selects the item with the most time across all lists.
+    fn jump_to_longest_item(&mut self) {
+        let longest = self
+            .group_list
+            .items
+            .iter()
+            .enumerate()
+            .flat_map(|(list_index, list)| {
+                list.list
+                    .items
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, item)| (list_index, index, item))
+            })
+            .filter(|(_, _, item)| item.is_task())
+            .max_by_key(|(_, _, item)| item.duration)
+            .map(|(list_index, index, item)| {
+                (list_index, index, item.title.clone(), item.duration)
+            });
+        if let Some((list_index, index, title, duration)) = longest {
+            self.focus_item(list_index, index);
+            self.notify(format!(
+                "Most time: \"{}\" with {}",
+                title,
+                format_duration(duration, self.config.duration_precision)
+            ));
+        }
+    }
+
+    /// Selects the list with the most time.
+    fn jump_to_longest_list(&mut self) {
+        let longest = self
+            .group_list
+            .items
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, list)| list.total_duration())
+            .map(|(index, list)| (index, list.name.clone(), list.total_duration()));
+        if let Some((index, name, duration)) = longest {
+            if let Some(active) = self.active_list.take() {
+                self.group_list.items[active].list.state.select(None);
+            }
+            self.group_list.state.select(Some(index));
+            self.notify(format!(
+                "Most time: \"{}\" with {}",
+                name,
+                format_duration(duration, self.config.duration_precision)
+            ));
+        }
+    }
+
+    fn focus_next_running(&mut self) {
+        let running = self.running_positions();
+
+        let current = self.selected_item();
+        let next = running
+            .iter()
+            .find(|position| Some(**position) > current)
+            .or_else(|| running.first());
+        if let Some((list_index, index)) = next.copied() {
+            self.focus_item(list_index, index);
+        }
+    }
+
+    fn navigate(&mut self, forward: bool) {
+        let wrap = self.config.wrap_navigation;
+        let skip_done = self.config.skip_done_in_nav;
+        let skip_dividers = self.config.skip_dividers;
+        let filter = self.filter;
+        let matches = |item: &Item| {
+            filter.matches(item)
+                && (!skip_done || !item.done())
+                && (!skip_dividers || item.is_task())
+        };
+        if let (Some(pos), true) = (self.active_list, self.group_by != GroupBy::None) {
+            // Grouped lists are shown out of order, so step through them as shown.
+            let list = &self.group_list.items[pos].list;
+            let order = self
+                .display_order(pos)
+                .into_iter()
+                .filter(|index| matches(&list.items[*index]))
+                .collect::<Vec<_>>();
+            let current = list
+                .state
+                .selected()
+                .and_then(|selected| order.iter().position(|index| *index == selected));
+            let next = match current {
+                Some(current) if forward && current + 1 < order.len() => Some(current + 1),
+                Some(current) if !forward && current > 0 => Some(current - 1),
+                Some(_) if wrap => Some(if forward { 0 } else { order.len() - 1 }),
+                Some(current) => Some(current),
+                None if order.is_empty() => None,
+                None => Some(0),
+            };
+            if let Some(next) = next {
+                self.group_list.items[pos]
+                    .list
+                    .state
+                    .select(Some(order[next]));
+            }
+        } else if let Some(pos) = self.active_list {
+            let list = &mut self.group_list.items[pos].list;
+            if forward {
+                list.next_matching(wrap, matches);
+            } else {
+                list.previous_matching(wrap, matches);
+            }
+        } else if forward {
+            self.group_list.next_matching(wrap, |_| true);
+        } else {
+            self.group_list.previous_matching(wrap, |_| true);
+        }
+    }
+
+    fn move_selected(&mut self, direction: ListDirection) {
+        if self.config.move_across_lists && self.move_across_lists(&direction) {
+            return;
+        }
+        let moved = if let Some(index) = self.active_list {
+            let list = &mut self.group_list.items.get_mut(index).unwrap().list;
+            list.move_selected_item(direction)
+        } else {
+            self.group_list.move_selected_item(direction)
+        };
+        if let Some((from, to)) = moved {
+            self.record(Operation::Move {
+                list_index: self.active_list,
+                from,
+                to,
+            });
+        }
+    }
+
+    /// Moves the selected item past the edge of its list into the one above,
+    /// where it goes last, or the one below, where it goes first. The
+    /// selection follows it. Returns false while it is not at an edge.
+    fn move_across_lists(&mut self, direction: &ListDirection) -> bool {
+        let from_list = match self.active_list {
+            Some(from_list) => from_list,
+            None => return false,
+        };
+        let list = &self.group_list.items[from_list].list;
+        let from = match list.state.selected() {
+            Some(from) => from,
+            None => return false,
+        };
+        let up = matches!(direction, ListDirection::Down);
+        let to_list = if up && from == 0 {
+            from_list.checked_sub(1)
+        } else if !up && from + 1 == list.items.len() {
+            Some(from_list + 1).filter(|index| *index < self.group_list.items.len())
+        } else {
+            return false;
+        };
+        let to_list = match to_list {
+            Some(to_list) => to_list,
+            None => {
+                self.notify(format!(
+                    "No list {} this one",
+                    if up { "above" } else { "below" }
+                ));
+                return true;
+            }
+        };
+
+        let mut item = self.group_list.items[from_list].list.items.remove(from);
+        self.group_list.items[from_list].list.clamp_selection();
+        item.order = 0;
+        let target = &mut self.group_list.items[to_list].list;
+        let to = if up { target.items.len() } else { 0 };
+        target.items.insert(to, item);
+        target.state.select(Some(to));
+        self.active_list = Some(to_list);
+        self.group_list.state.select(Some(to_list));
+        self.record(Operation::Transfer {
+            from_list,
+            from,
+            to_list,
+            to,
+        });
+        true
+    }
+
+    /// Takes the selected item or list out to paste it elsewhere. Only one
+    /// thing can be cut at a time so nothing gets lost.
+    fn cut_selected(&mut self) {
+        if self.cut.is_some() {
+            self.notify("Paste with p or put the cut back with Ctrl+z first");
+            return;
+        }
+        if self.active_list.is_some() {
+            if let Some((list_index, index)) = self.selected_item() {
+                if self.selected_locked() {
+                    return;
+                }
+                let list = &mut self.group_list.items[list_index].list;
+                if let Some(item) = list.remove_selected() {
+                    self.cut = Some(Cut::Item {
+                        list_index,
+                        index,
+                        item,
+                    });
+                }
+            }
+        } else if let Some(index) = self.group_list.state.selected() {
+            if let Some(list) = self.group_list.remove_selected() {
+                self.cut = Some(Cut::Group { index, list });
+            }
+        }
+    }
+
+    fn paste(&mut self) {
+        match (self.active_list, self.cut.take()) {
+            (
+                Some(to_list),
+                Some(Cut::Item {
+                    list_index,
+                    index,
+                    mut item,
+                }),
+            ) => {
+                // Its place in the old list means nothing here.
+                item.order = 0;
+                let list = &mut self.group_list.items[to_list].list;
+                list.insert_at_selected(item);
+                let to = list.state.selected().unwrap_or_default();
+                self.record(if list_index == to_list {
+                    Operation::Reinsert {
+                        list_index: Some(to_list),
+                        from: index,
+                        to,
+                    }
+                } else {
+                    Operation::Transfer {
+                        from_list: list_index,
+                        from: index,
+                        to_list,
+                        to,
+                    }
+                });
+            }
+            (None, Some(Cut::Group { index, list })) => {
+                self.group_list.insert_at_selected(list);
+                let to = self.group_list.state.selected().unwrap_or_default();
+                self.record(Operation::Reinsert {
+                    list_index: None,
+                    from: index,
+                    to,
+                });
+            }
+            (_, cut) => self.cut = cut,
+        }
+    }
+
+    /// Puts what is cut back where it was taken from, returning whether
+    /// there was anything to put back.
+    pub fn put_back_cut(&mut self) -> bool {
+        match self.cut.take() {
+            Some(Cut::Group { index, list }) => {
+                let index = index.min(self.group_list.items.len());
+                self.group_list.items.insert(index, list);
+                if self.active_list.is_none() {
+                    self.group_list.state.select(Some(index));
+                }
+                true
+            }
+            Some(Cut::Item {
+                list_index,
+                index,
+                item,
+            }) => match self.group_list.items.get_mut(list_index) {
+                Some(group_list) => {
+                    let list = &mut group_list.list;
+                    let index = index.min(list.items.len());
+                    list.items.insert(index, item);
+                    if self.active_list == Some(list_index) {
+                        list.state.select(Some(index));
+                    }
+                    true
+                }
+                None => {
+                    self.cut = Some(Cut::Item {
+                        list_index,
+                        index,
+                        item,
+                    });
+                    false
+                }
+            },
+            None => false,
+        }
+    }
+
+    /// Starts picking an item of another list to swap the selected one with.
+    fn pick_swap_target(&mut self) {
+        match self.selected_item() {
+            Some((list_index, index)) if !self.selected_locked() => {
+                self.overview = Some((Overview::Swap { list_index, index }, 0));
+            }
+            Some(_) => self.notify("Locked items stay in place".to_string()),
+            None => {}
+        }
+    }
+
+    /// Lets two items trade places and follows the first one to its new
+    /// place.
+    pub fn swap_items(&mut self, first: (usize, usize), second: (usize, usize)) {
+        if self.exchange_items(first, second).is_none() {
+            return;
+        }
+        self.record(Operation::Swap {
+            first_list: first.0,
+            first: first.1,
+            second_list: second.0,
+            second: second.1,
+        });
+        self.focus_item(second.0, second.1);
+    }
+
+    /// Exchanges two items along with their manual order.
+    pub fn exchange_items(
+        &mut self,
+        (first_list, first): (usize, usize),
+        (second_list, second): (usize, usize),
+    ) -> Option<()> {
+        self.group_list
+            .items
+            .get(first_list)?
+            .list
+            .items
+            .get(first)?;
+        self.group_list
+            .items
+            .get(second_list)?
+            .list
+            .items
+            .get(second)?;
+        if first_list == second_list {
+            self.group_list.items[first_list]
+                .list
+                .items
+                .swap(first, second);
+        } else {
+            let lists = &mut self.group_list.items;
+            let item = std::mem::take(&mut lists[first_list].list.items[first]);
+            let other = std::mem::replace(&mut lists[second_list].list.items[second], item);
+            lists[first_list].list.items[first] = other;
+        }
+        let first_order = self.group_list.items[first_list].list.items[first].order;
+        let second_order = self.group_list.items[second_list].list.items[second].order;
+        self.group_list.items[first_list].list.items[first].order = second_order;
+        self.group_list.items[second_list].list.items[second].order = first_order;
+        Some(())
+    }
+
+    /// Whether `collapse_empty_detail` hides the detail pane: outside a list
+    /// it previews the selected list, which may have no items to show.
+    fn detail_empty(&self) -> bool {
+        let filter = self.filter;
+        let shown = self
+            .group_list
+            .state
+            .selected()
+            .and_then(|index| self.group_list.items.get(index))
+            .map(|list| list.list.items.iter().any(|item| filter.matches(item)))
+            .unwrap_or_default();
+        self.config.collapse_empty_detail && !shown
+    }
+
+    /// Sends the selected list or item to the top or bottom.
+    fn move_selected_to_end(&mut self, top: bool) {
+        let moved = match (self.active_list, top) {
+            (Some(index), true) => self.group_list.items[index].list.move_to_top(),
+            (Some(index), false) => self.group_list.items[index].list.move_to_bottom(),
+            (None, true) => self.group_list.move_to_top(),
+            (None, false) => self.group_list.move_to_bottom(),
+        };
+        if let Some((from, to)) = moved {
+            self.record(Operation::Reinsert {
+                list_index: self.active_list,
+                from,
+                to,
+            });
+        }
+    }
+
+    /// Adds the item to the configured inbox list, creating the list if needed.
+    fn capture(&mut self, item: Item) {
+        let name = self
+            .config
+            .inbox
+            .clone()
+            .unwrap_or_else(|| "Inbox".to_string());
+        let mut operations = Vec::new();
+        let index = match self
+            .group_list
+            .items
+            .iter()
+            .position(|list| list.name == name)
+        {
+            Some(index) => index,
+            None => {
+                let list = self.new_list(name.clone());
+                operations.push(Operation::InsertGroup { id: list.id });
+                self.group_list.items.push(list);
+                self.group_list.items.len() - 1
+            }
+        };
+        operations.push(Operation::InsertItem { id: item.id });
+        self.group_list.items[index].list.items.push(item);
+        self.record(Operation::Batch { operations });
+        self.notify(format!("Captured to \"{}\"", name));
+    }
+
+    fn apply_list_settings(&mut self) {
+        let rate = self.dialog.input.title.trim();
+        let rate = if rate.is_empty() {
+            None
+        } else {
+            match rate.parse::<f64>() {
+                Ok(rate) if rate >= 0.0 => Some(rate),
+                _ => {
+                    self.notify(format!("Invalid hourly rate \"{}\"", rate));
+                    return;
+                }
+            }
+        };
+        let currency = self.dialog.input.desc.trim().to_string();
+        let timebox = self.dialog.target.trim();
+        let timebox_minutes = if timebox.is_empty() {
+            None
+        } else {
+            match self.dialog.target_millis() {
+                Some(millis) if Duration::milliseconds(millis).num_minutes() > 0 => {
+                    Some(Duration::milliseconds(millis).num_minutes())
+                }
+                _ => {
+                    self.notify(format!("Invalid timebox \"{}\"", timebox));
+                    return;
+                }
+            }
+        };
+        let wip = self.dialog.points.trim();
+        let wip_limit = if wip.is_empty() {
+            None
+        } else {
+            match wip.parse::<usize>() {
+                Ok(limit) if limit > 0 => Some(limit),
+                _ => {
+                    self.notify(format!("Invalid WIP limit \"{}\"", wip));
+                    return;
+                }
+            }
+        };
+        let color = self.dialog.tags.trim().to_lowercase();
+        let color = if color.is_empty() {
+            None
+        } else if parse_color(&color).is_some() {
+            Some(color)
+        } else {
+            self.notify(format!("Unknown color \"{}\"", color));
+            return;
+        };
+        let icon = self
+            .dialog
+            .link
+            .trim()
+            .graphemes(true)
+            .next()
+            .map(str::to_string);
+        let project_code = self.dialog.code();
+        let rounding = self.dialog.rounding.trim();
+        let display_rounding = if rounding.is_empty() {
+            None
+        } else {
+            match rounding.parse::<i64>() {
+                Ok(minutes) if minutes > 0 => Some(minutes),
+                _ => {
+                    self.notify(format!("Invalid rounding \"{}\"", rounding));
+                    return;
+                }
+            }
+        };
+        let budget = self.dialog.budget.trim();
+        let weekly_budget = if budget.is_empty() {
+            None
+        } else {
+            match parse_duration(budget) {
+                Some(millis) => Some(Duration::milliseconds(millis).num_minutes()),
+                None => {
+                    self.notify(format!("Invalid weekly budget \"{}\"", budget));
+                    return;
+                }
+            }
+        };
+        if let Some(list) = self
+            .group_list
+            .state
+            .selected()
+            .and_then(|index| self.group_list.items.get_mut(index))
+        {
+            list.hourly_rate = rate;
+            list.currency = currency;
+            list.timebox_minutes = timebox_minutes;
+            list.wip_limit = wip_limit;
+            list.color = color;
+            list.icon = icon;
+            list.display_rounding = display_rounding;
+            list.weekly_budget = weekly_budget;
+            list.project_code = project_code;
+        }
+    }
+
+    /// Turns the selected item into a new list right after the current one,
+    /// with its subtasks as the new list's items.
+    fn promote_selected(&mut self) {
+        let (list_index, index) = match self.selected_item() {
+            Some(selected) if !self.selected_locked() => selected,
+            _ => return,
+        };
+        if let Some(operation) = self.promote_item(list_index, index) {
+            if let Operation::Promote { item, .. } = &operation {
+                self.notify(format!("Promoted \"{}\" to a list", item.title));
+            }
+            self.record(operation);
+        }
+    }
+
+    /// Does the promotion of `promote_selected` and returns the operation
+    /// undoing it. Time tracked on the item and its description stay on an
+    /// item of the same name at the top of the new list, and all new items
+    /// get its tags.
+    pub fn promote_item(&mut self, list_index: usize, index: usize) -> Option<Operation> {
+        let list = &mut self.group_list.items.get_mut(list_index)?.list;
+        if index >= list.items.len() {
+            return None;
+        }
+        let item = list.items.remove(index);
+        list.state.select(None);
+
+        let mut group = self.new_list(item.title.clone());
+        if item.duration > 0 || !item.desc.is_empty() {
+            group.list.items.push(Item {
+                subtasks: Vec::new(),
+                ..item.clone()
+            });
+        }
+        let now = Local::now();
+        for subtask in &item.subtasks {
+            let mut new_item = Item {
+                id: self.new_id(),
+                title: subtask.title.clone(),
+                tags: item.tags.clone(),
+                ..Item::default()
+            };
+            if subtask.done {
+                new_item.start_at = Some(now);
+                new_item.end_at = Some(now);
+            }
+            group.list.items.push(new_item);
+        }
+        if !group.list.items.is_empty() {
+            group.list.state.select(Some(0));
+        }
+
+        let new_index = list_index + 1;
+        self.group_list.items.insert(new_index, group);
+        self.active_list = Some(new_index);
+        self.group_list.state.select(Some(new_index));
+        Some(Operation::Promote {
+            list_index,
+            index,
+            item,
+        })
+    }
+
+    /// The item the timer keys act on: the selected task, or outside a list
+    /// the first open task of the selected list.
+    fn timer_target(&self) -> Option<(usize, Item)> {
+        let (list_index, item) = match (self.active_list, self.selected_item()) {
+            (Some(_), Some((list_index, index))) => (
+                list_index,
+                &self.group_list.items[list_index].list.items[index],
+            ),
+            (Some(_), None) => return None,
+            (None, _) => {
+                let list_index = self.group_list.state.selected()?;
+                let item = self
+                    .group_list
+                    .items
+                    .get(list_index)?
+                    .list
+                    .items
+                    .iter()
+                    .find(|item| item.is_task() && !item.done() && !item.locked)?;
+                (list_index, item)
+            }
+        };
+        Some((list_index, item.clone()))
+    }
+
+    /// Applies a timer key and records the status change of its item, so it
+    /// can be undone.
+    fn undoable_status(&mut self, change: impl FnOnce(&mut App)) {
+        let (list_index, previous) = match self.timer_target() {
+            Some(target) => target,
+            None => return change(self),
+        };
+        change(self);
+        let status = |item: &Item| (item.start_at, item.end_at, item.paused);
+        let changed = self
+            .group_list
+            .items
+            .get(list_index)
+            .and_then(|list| list.list.items.iter().find(|item| item.id == previous.id))
+            .map(|item| status(item) != status(&previous))
+            .unwrap_or_default();
+        if changed {
+            self.record(Operation::Edit {
+                list_index,
+                item: previous,
+            });
+        }
+    }
+
+    /// Starts the item the timer keys act on, then pauses or resumes it, so
+    /// repeated presses never throw its time away. Done items are reset.
+    fn start_or_pause(&mut self) {
+        let list_index = self
+            .active_list
+            .or_else(|| self.group_list.state.selected());
+        let wip_full = list_index
+            .and_then(|index| self.group_list.items.get(index))
+            .map(GroupList::wip_full)
+            .unwrap_or_default();
+        let confirm_done = self.config.confirm_restart_done;
+        let item = if self.active_list.is_some() {
+            self.get_selected_task()
+        } else {
+            self.next_action()
+        };
+        if let Some(item) = item {
+            if let (Some(list_index), true) = (list_index, confirm_done && item.done()) {
+                let id = item.id;
+                self.confirm = Some(Confirm::ResetTimer { list_index, id });
+            } else if item.done() {
+                item.reset();
+            } else if item.started() {
+                item.toggle_pause();
+            } else if let (Some(list_index), true) = (list_index, wip_full) {
+                let id = item.id;
+                self.confirm = Some(Confirm::StartOverWip { list_index, id });
+            } else {
+                item.start();
+            }
+        }
+    }
+
+    fn toggle_done(&mut self) {
+        if let Some(item) = self.get_selected_task() {
+            let done = if item.end_at.is_some() {
+                item.end_at = None;
+                None
+            } else {
+                item.end_at = Some(Local::now());
+                Some(item.clone())
+            };
+            let id = item.id;
+            let list_index = self.active_list.unwrap_or_default();
+            if self.config.auto_sink_done {
+                self.sink(list_index, id, done.is_some());
+            }
+            if let Some(item) = done {
+                let list_name = self.group_list.items[list_index].name.clone();
+                self.run_done_hook(&list_name, &item);
+                if self.config.note_on_stop && !self.dialog.displayed() {
+                    self.dialog
+                        .display(DialogState::StopNote { list_index, id });
+                }
+            }
+            self.reselect(id);
+        }
+    }
+
+    /// Moves a done item to the end of its list, or a reopened one back to
+    /// where it sank from.
+    fn sink(&mut self, list_index: usize, id: u64, done: bool) {
+        let list = &mut self.group_list.items[list_index].list;
+        let index = match list.items.iter().position(|item| item.id == id) {
+            Some(index) => index,
+            None => return,
+        };
+        if done {
+            if index + 1 < list.items.len() {
+                let item = list.items.remove(index);
+                list.items.push(item);
+                self.sunk.insert(id, index);
+            }
+        } else if let Some(from) = self.sunk.remove(&id) {
+            let item = list.items.remove(index);
+            list.items.insert(from.min(list.items.len()), item);
+        }
+    }
+
+    /// Moves the selected item on from not started to running, paused, done
+    /// and back to not started.
+    fn cycle_status(&mut self) {
+        let list_index = match self.active_list {
+            Some(index) => index,
+            None => return,
+        };
+        let wip_full = self.group_list.items[list_index].wip_full();
+        let confirm_done = self.config.confirm_restart_done;
+        let item = match self.get_selected_task() {
+            Some(item) => item,
+            None => return,
+        };
+        let id = item.id;
+        if item.done() {
+            if confirm_done {
+                self.confirm = Some(Confirm::ResetTimer { list_index, id });
+            } else {
+                item.reset();
+            }
+        } else if item.started() && item.paused {
+            item.paused = false;
+            self.toggle_done();
+        } else if item.started() {
+            item.toggle_pause();
+        } else if wip_full {
+            self.confirm = Some(Confirm::StartOverWip { list_index, id });
+        } else {
+            item.start();
+        }
+    }
+
+    /// Starts the selected item, or pauses / resumes it once started. Done
+    /// items are left alone, restarting them stays with Space and Alt+s.
+    fn toggle_timer(&mut self) {
+        let list_index = match self.active_list {
+            Some(index) => index,
+            None => return,
+        };
+        let wip_full = self.group_list.items[list_index].wip_full();
+        let item = match self.get_selected_task() {
+            Some(item) => item,
+            None => return,
+        };
+        let id = item.id;
+        if item.done() {
+            self.notify("Item is done, restart it with Space or Alt+s".to_string());
+        } else if item.started() {
+            item.toggle_pause();
+        } else if wip_full {
+            self.confirm = Some(Confirm::StartOverWip { list_index, id });
+        } else {
+            item.start();
+        }
+    }
+
+    /// Adds `delta` percent to the selected item's progress, within 0 to 100.
+    fn change_progress(&mut self, delta: i16) {
+        if let Some(item) = self.get_selected_task() {
+            item.progress = (item.progress as i16 + delta).clamp(0, 100) as u8;
+        }
+    }
+
+    /// Moves the time after `split` of the selected item into a new stopped
+    /// item right below it, so the two parts can be labeled separately.
+    fn split_selected(&mut self, split: i64, title: String) {
+        let (list_index, index) = match self.selected_item() {
+            Some(selected) if !self.selected_locked() => selected,
+            _ => return,
+        };
+        let id = self.new_id();
+        let list = &mut self.group_list.items[list_index].list;
+        let item = &mut list.items[index];
+        if split >= item.duration {
+            let message = format!(
+                "Split point must be below the current {}",
+                format_duration(item.duration, self.config.duration_precision)
+            );
+            self.notify(message);
+            return;
+        }
+
+        let previous = item.clone();
+        let moved = item.duration - split;
+        let moved_today = item.tracked_today.min(moved);
+        item.duration = split;
+        item.tracked_today -= moved_today;
+        let new_item = Item {
+            id,
+            title: if title.is_empty() {
+                item.title.clone()
+            } else {
+                title
+            },
+            start_at: item.start_at,
+            end_at: Some(item.end_at.unwrap_or_else(Local::now)),
+            duration: moved,
+            tags: item.tags.clone(),
+            tracked_on: item.tracked_on,
+            tracked_today: moved_today,
+            ..Item::default()
+        };
+        let message = format!(
+            "Split off {} into \"{}\"",
+            format_duration(moved, self.config.duration_precision),
+            new_item.title
+        );
+        list.items.insert(index + 1, new_item);
+        self.notify(message);
+        self.record(Operation::Batch {
+            operations: vec![
+                Operation::Edit {
+                    list_index,
+                    item: previous,
+                },
+                Operation::InsertItem { id },
+            ],
+        });
+    }
+
+    /// Pauses every running item of all lists.
+    pub fn pause_all(&mut self) {
+        for item in self
+            .group_list
+            .items
+            .iter_mut()
+            .flat_map(|list| list.list.items.iter_mut())
+            .filter(|item| item.running())
+        {
+            item.paused = true;
+        }
+    }
+
+    /// Caps durations that cannot be right: no item can have tracked more
+    /// than the time between its start and its end (or now), and today's
+    /// time is part of the total. The lost time is not rebuilt from the log.
+    /// Items without a start, like imported ones, have no span to check
+    /// against. Returns how many items were capped.
+    pub fn cap_durations(&mut self) -> usize {
+        let now = Local::now();
+        let mut capped = 0;
+        for item in self
+            .group_list
+            .items
+            .iter_mut()
+            .flat_map(|list| list.list.items.iter_mut())
+        {
+            let duration = match item.start_at {
+                Some(start_at) => {
+                    let span = (item.end_at.unwrap_or(now) - start_at).num_milliseconds();
+                    item.duration.clamp(0, span.max(0))
+                }
+                None => item.duration.max(0),
+            };
+            let tracked_today = item.tracked_today.clamp(0, duration);
+            if duration != item.duration || tracked_today != item.tracked_today {
+                item.duration = duration;
+                item.tracked_today = tracked_today;
+                item.over_estimate = item.over_estimate && item.over_target();
+                capped += 1;
+            }
+        }
+        capped
+    }
+
+    /// Pauses every running item of the active list, or resumes the ones
+    /// paused that way if none is running.
+    fn toggle_list_timers(&mut self) {
+        let list = match self
+            .active_list
+            .and_then(|index| self.group_list.items.get_mut(index))
+        {
+            Some(list) => list,
+            None => return,
+        };
+        let running = list
+            .list
+            .items
+            .iter()
+            .filter(|item| item.running() && !item.locked)
+            .map(|item| item.id)
+            .collect::<Vec<_>>();
+
+        let message = if !running.is_empty() {
+            for item in &mut list.list.items {
+                if running.contains(&item.id) {
+                    item.paused = true;
+                }
+            }
+            let message = format!("Paused {} timers", running.len());
+            list.paused_ids = running;
+            message
+        } else {
+            let mut resumed = 0;
+            for item in &mut list.list.items {
+                if list.paused_ids.contains(&item.id) && item.paused && !item.done() {
+                    item.paused = false;
+                    resumed += 1;
+                }
+            }
+            list.paused_ids.clear();
+            format!("Resumed {} timers", resumed)
+        };
+        self.notify(message);
+    }
+
+    fn open_link(&mut self) {
+        let link = match self.get_selected_item() {
+            Some(item) => item.link.clone(),
+            None => return,
+        };
+        self.notify(match link {
+            Some(link) => match open::that(&link) {
+                Ok(_) => format!("Opened {}", link),
+                Err(err) => format!("Could not open {}: {}", link, err),
+            },
+            None => "The item has no link".to_string(),
+        });
+    }
+
+    /// Pinned items are kept above unpinned ones, otherwise keeping their order.
+    fn toggle_pinned(&mut self) {
+        if let Some((list_index, index)) = self.selected_item() {
+            let list = &mut self.group_list.items[list_index].list;
+            let item = &mut list.items[index];
+            item.pinned = !item.pinned;
+            let id = item.id;
+            list.items.sort_by_key(|item| !item.pinned);
+            self.reselect(id);
+        }
+    }
+
+    /// Marks the selected task as waiting on something, or no longer.
+    fn toggle_blocked(&mut self) {
+        let message = match self.get_selected_task() {
+            Some(item) if item.blocked_at.is_some() => {
+                item.blocked_at = None;
+                format!("\"{}\" is no longer blocked", item.title)
+            }
+            Some(item) => {
+                item.blocked_at = Some(Local::now());
+                format!("\"{}\" is blocked", item.title)
+            }
+            None => return,
+        };
+        self.notify(message);
+    }
+
+    fn toggle_hide_done(&mut self) {
+        let selected_id = self.get_selected_item().map(|item| item.id);
+        self.filter.hide_done = !self.filter.hide_done;
+        if let Some(id) = selected_id {
+            self.reselect(id);
+        }
+    }
+
+    fn toggle_show_scheduled(&mut self) {
+        let selected_id = self.get_selected_item().map(|item| item.id);
+        self.filter.show_scheduled = !self.filter.show_scheduled;
+        if let Some(id) = selected_id {
+            self.reselect(id);
         }
     }
 
     pub fn add_time(&mut self, duration: std::time::Duration) {
+        if self.all_paused {
+            return;
+        }
+        let count_paused = self.config.count_paused;
+        let mut running = 0;
+        let mut logged = 0;
+        let mut over_estimate = None;
+        let mut alarm = None;
         for list in &mut self.group_list.items {
             for item in &mut list.list.items {
-                if item.start_at.is_some() && item.end_at.is_none() && !item.paused {
-                    if let Ok(time) = Duration::from_std(duration) {
-                        item.duration += time.num_milliseconds();
+                // The item's own timer and each running one of its timers
+                // add up, so its duration is their sum.
+                let mut added = 0;
+                if let Ok(time) = Duration::from_std(duration) {
+                    if item.counting(count_paused) {
+                        added += time.num_milliseconds();
+                    }
+                    if !item.done() {
+                        for timer in item.timers.iter_mut().filter(|timer| timer.running) {
+                            timer.duration += time.num_milliseconds();
+                            added += time.num_milliseconds();
+                        }
+                    }
+                }
+                if added > 0 {
+                    running += 1;
+                    // An alarm rings when the duration steps over it, so it
+                    // fires once and again only after the time went back.
+                    let before = item.duration;
+                    item.duration += added;
+                    if let Some(passed) = item
+                        .alarms
+                        .iter()
+                        .rev()
+                        .find(|alarm| before < **alarm && **alarm <= item.duration)
+                    {
+                        alarm = Some((item.title.clone(), *passed));
+                    }
+                    item.track_today(added);
+                    logged += added;
+                    if !item.over_estimate && item.over_target() {
+                        item.over_estimate = true;
+                        over_estimate = Some(item.title.clone());
+                    }
+                    if item.auto_stop_at_target && item.over_target() {
+                        item.end_at = Some(Local::now());
                     }
                 }
             }
         }
+        if let Some(title) = over_estimate {
+            self.notify(format!("\"{}\" is over its target", title));
+        }
+        if let Some((title, passed)) = alarm {
+            self.notify(format!(
+                "\"{}\" reached its {} alarm",
+                title,
+                format_duration(passed, DurationPrecision::Minutes)
+            ));
+            self.reminder_due = true;
+        }
+        if logged > 0 {
+            self.log_day(logged);
+        }
+        self.check_reminders();
+        self.activity.record(running);
+        if let Ok(time) = Duration::from_std(duration) {
+            self.tick_timebox(time.num_milliseconds());
+        }
+    }
+
+    /// Announces running items whose duration crossed another multiple of the
+    /// reminder interval. Items seen for the first time only remember where
+    /// they are, so starting the app does not remind of old time.
+    fn check_reminders(&mut self) {
+        let interval = match self.config.reminder_interval {
+            Some(minutes) if minutes > 0 => Duration::minutes(minutes).num_milliseconds(),
+            _ => return,
+        };
+        let mut reminder = None;
+        for item in self
+            .group_list
+            .items
+            .iter()
+            .flat_map(|list| list.list.items.iter())
+            .filter(|item| item.running())
+        {
+            let passed = item.duration / interval;
+            match self.reminded.entry(item.id) {
+                Entry::Vacant(entry) => {
+                    entry.insert(passed);
+                }
+                Entry::Occupied(mut entry) => {
+                    if passed > *entry.get() {
+                        reminder = Some(format!(
+                            "You've been on \"{}\" for {}",
+                            item.title,
+                            format_duration(passed * interval, self.config.duration_precision)
+                        ));
+                    }
+                    entry.insert(passed);
+                }
+            }
+        }
+        if let Some(reminder) = reminder {
+            self.notify(reminder);
+            self.reminder_due = true;
+        }
+    }
+
+    /// No key was pressed for the configured idle time while a timer runs.
+    fn idle(&self) -> bool {
+        let minutes = self.config.idle_minutes;
+        match self.last_input {
+            Some(last_input) if minutes > 0 && !self.all_paused => {
+                Local::now() - last_input > Duration::minutes(minutes)
+                    && self
+                        .group_list
+                        .items
+                        .iter()
+                        .any(|list| list.list.items.iter().any(Item::running))
+            }
+            _ => false,
+        }
+    }
+
+    /// Pauses or resumes every timer at once, each keeps its own state.
+    fn toggle_all_paused(&mut self) {
+        self.all_paused = !self.all_paused;
+        self.notify(if self.all_paused {
+            "All timers paused"
+        } else {
+            "Timers resumed"
+        });
+    }
+
+    /// A banner across the top while all timers are paused.
+    fn draw_paused_banner<B: Backend>(&self, frame: &mut Frame<B>) {
+        if !self.all_paused {
+            return;
+        }
+        let size = frame.size();
+        let banner = Paragraph::new(Span::styled(
+            " PAUSED · Ctrl+c: resume ",
+            Style::default()
+                .fg(Color::White)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .alignment(Alignment::Center);
+        frame.render_widget(banner, Rect::new(size.x, size.y, size.width, 1));
+    }
+
+    /// Returns true once per reminder if the terminal bell should ring.
+    pub fn reminder_bell(&mut self) -> bool {
+        let due = self.reminder_due;
+        self.reminder_due = false;
+        due
+    }
+
+    /// Returns true once per idle stretch if the terminal bell should ring.
+    pub fn idle_bell(&mut self) -> bool {
+        if self.config.idle_bell && !self.idle_alerted && self.idle() {
+            self.idle_alerted = true;
+            return true;
+        }
+        false
     }
 
-    fn selected_item(&self) -> Option<(usize, usize)> {
+    pub fn selected_item(&self) -> Option<(usize, usize)> {
         if let Some(list_index) = self.active_list {
             if let Some(list) = self.group_list.items.get(list_index) {
                 if let Some(index) = list.list.state.selected() {
@@ -201,7 +3037,27 @@ impl<'a> App {
         }
     }
 
-    fn get_selected_item(&mut self) -> Option<&mut Item> {
+    pub fn find_item(&self, list_index: usize, id: u64) -> Option<&Item> {
+        self.group_list
+            .items
+            .get(list_index)?
+            .list
+            .items
+            .iter()
+            .find(|item| item.id == id)
+    }
+
+    pub fn find_item_mut(&mut self, list_index: usize, id: u64) -> Option<&mut Item> {
+        self.group_list
+            .items
+            .get_mut(list_index)?
+            .list
+            .items
+            .iter_mut()
+            .find(|item| item.id == id)
+    }
+
+    pub fn get_selected_item(&mut self) -> Option<&mut Item> {
         if let Some((list_index, index)) = self.selected_item() {
             self.get_item(list_index, index)
         } else {
@@ -209,10 +3065,57 @@ impl<'a> App {
         }
     }
 
+    pub fn get_selected_unlocked(&mut self) -> Option<&mut Item> {
+        self.get_selected_item().filter(|item| !item.locked)
+    }
+
+    fn get_selected_task(&mut self) -> Option<&mut Item> {
+        self.get_selected_unlocked().filter(|item| item.is_task())
+    }
+
+    fn selected_locked(&self) -> bool {
+        match self.selected_item() {
+            Some((list_index, index)) => self.group_list.items[list_index].list.items[index].locked,
+            None => false,
+        }
+    }
+
+    /// The first open task of the selected group, used for timer actions
+    /// while not inside a list.
+    fn next_action(&mut self) -> Option<&mut Item> {
+        let index = self.group_list.state.selected()?;
+        self.group_list
+            .items
+            .get_mut(index)?
+            .list
+            .items
+            .iter_mut()
+            .find(|item| item.is_task() && !item.done() && !item.locked)
+    }
+
     fn show_dialog<B: Backend>(&mut self, frame: &mut Frame<B>) {
         let size = frame.size();
-        let dialog_title = if self.active_list.is_some() {
-            " New Item "
+        let dialog_title = if self.dialog.replacing() {
+            " Replace in all items "
+        } else if self.dialog.adding_subtask() {
+            " New Subtask "
+        } else if self.dialog.adding_timer() {
+            " New Timer "
+        } else if self.dialog.editing_meta() {
+            " Metadata "
+        } else if self.dialog.splitting() {
+            " Split Time "
+        } else if self.dialog.stop_note().is_some() {
+            " Stop Note "
+        } else if self.dialog.editing_list() {
+            " List Settings "
+        } else if self.dialog.capturing() {
+            " Quick Capture "
+        } else if self.active_list.is_some() {
+            match self.dialog.input.kind {
+                ItemKind::Task => " New Item ",
+                ItemKind::Divider => " New Divider (Ctrl+v) ",
+            }
         } else {
             " New List "
         };
@@ -222,224 +3125,1620 @@ impl<'a> App {
             .borders(Borders::ALL)
             .style(Style::default().bg(Color::Blue));
 
+        let mut fields = Vec::new();
+        if self.dialog.replacing() {
+            let case = if self.dialog.case_sensitive {
+                "case sensitive"
+            } else {
+                "ignoring case"
+            };
+            fields.push((
+                format!("Search, {} (Ctrl+t)", case),
+                self.dialog.input.title.clone(),
+                Input::Titel,
+            ));
+            fields.push((
+                "Replace with".to_string(),
+                self.dialog.input.desc.clone(),
+                Input::Desc,
+            ));
+        } else if self.dialog.stop_note().is_some() {
+            fields.push((
+                "What happened? (Enter to skip)".to_string(),
+                self.dialog.input.title.clone(),
+                Input::Titel,
+            ));
+        } else if self.dialog.adding_timer() {
+            fields.push((
+                "Timer name".to_string(),
+                self.dialog.input.title.clone(),
+                Input::Titel,
+            ));
+        } else if self.dialog.splitting() {
+            fields.push((
+                "Title of the new item".to_string(),
+                self.dialog.input.title.clone(),
+                Input::Titel,
+            ));
+            fields.push((
+                "Split after (e.g. 1h30m)".to_string(),
+                self.dialog.target.clone(),
+                Input::Target,
+            ));
+        } else if self.dialog.editing_meta() {
+            fields.push((
+                "Field".to_string(),
+                self.dialog.input.title.clone(),
+                Input::Titel,
+            ));
+            fields.push((
+                "Value (empty to remove)".to_string(),
+                self.dialog.input.desc.clone(),
+                Input::Desc,
+            ));
+        } else if self.dialog.editing_list() {
+            fields.push((
+                "Hourly rate (empty to clear)".to_string(),
+                self.dialog.input.title.clone(),
+                Input::Titel,
+            ));
+            fields.push((
+                "Currency".to_string(),
+                self.dialog.input.desc.clone(),
+                Input::Desc,
+            ));
+            fields.push((
+                "Timebox per item (minutes)".to_string(),
+                self.dialog.target.clone(),
+                Input::Target,
+            ));
+            fields.push((
+                "WIP limit (empty for none)".to_string(),
+                self.dialog.points.clone(),
+                Input::Points,
+            ));
+            fields.push((
+                "Color (name or #rrggbb)".to_string(),
+                self.dialog.tags.clone(),
+                Input::Tags,
+            ));
+            fields.push((
+                "Icon (one character)".to_string(),
+                self.dialog.link.clone(),
+                Input::Link,
+            ));
+            fields.push((
+                "Round shown times to (minutes, e.g. 15)".to_string(),
+                self.dialog.rounding.clone(),
+                Input::Rounding,
+            ));
+            fields.push((
+                "Weekly budget (e.g. 20h, empty for none)".to_string(),
+                self.dialog.budget.clone(),
+                Input::Budget,
+            ));
+            fields.push((
+                "Project code".to_string(),
+                self.dialog.code.clone(),
+                Input::Code,
+            ));
+        } else {
+            fields.push((
+                "Title".to_string(),
+                self.dialog.input.title.clone(),
+                Input::Titel,
+            ));
+        }
+
+        if (self.active_list.is_some() || self.dialog.capturing()) && self.dialog.item_input() {
+            let auto_stop = if self.dialog.input.auto_stop_at_target {
+                "on"
+            } else {
+                "off"
+            };
+            fields.push((
+                format!(
+                    "Target (e.g. 1h30m), auto-stop {} (Ctrl+a), counts {} (Ctrl+w)",
+                    auto_stop,
+                    match self.dialog.input.mode {
+                        TimerMode::CountUp => "up",
+                        TimerMode::CountDown => "down",
+                    }
+                ),
+                self.dialog.target.clone(),
+                Input::Target,
+            ));
+            fields.push((
+                "Alarms (e.g. 15m, 30m, 1h)".to_string(),
+                self.dialog.alarms.clone(),
+                Input::Alarms,
+            ));
+            fields.push((
+                "Points".to_string(),
+                self.dialog.points.clone(),
+                Input::Points,
+            ));
+            fields.push((
+                "Due (YYYY-MM-DD HH:MM, tomorrow, friday, in 3 days)".to_string(),
+                self.dialog.due.clone(),
+                Input::Due,
+            ));
+            fields.push((
+                "Starts on (YYYY-MM-DD, monday, in 3 days), hidden until then".to_string(),
+                self.dialog.start.clone(),
+                Input::Start,
+            ));
+            fields.push((
+                "Tags (comma separated)".to_string(),
+                self.dialog.tags.clone(),
+                Input::Tags,
+            ));
+            fields.push((
+                "Link (URL or path)".to_string(),
+                self.dialog.link.clone(),
+                Input::Link,
+            ));
+            fields.push((
+                "Project code (empty for the list's)".to_string(),
+                self.dialog.code.clone(),
+                Input::Code,
+            ));
+            fields.push((
+                "Description".to_string(),
+                self.dialog.input.desc.clone(),
+                Input::Desc,
+            ));
+        }
+
+        let desc_fills =
+            !self.dialog.replacing() && !self.dialog.editing_list() && !self.dialog.editing_meta();
+
+        // Sized to the fields within the configured minimum and two thirds
+        // of the screen, or the whole screen if it is smaller than that.
+        let has_desc = fields.iter().any(|(_, _, input)| *input == Input::Desc);
+        let content_height = 2
+            + 2 * fields.len() as u16
+            + if desc_fills && has_desc {
+                DESC_LINES
+            } else {
+                0
+            };
+        // Borders, padding and the arrow before the focused label.
+        let content_width = 6 + fields
+            .iter()
+            .map(|(label, value, _)| label.width().max(value.width()))
+            .max()
+            .unwrap_or_default() as u16;
+        let width = content_width
+            .max(self.config.dialog_min_width)
+            .min((size.width * 2 / 3).max(self.config.dialog_min_width))
+            .min(size.width);
+        let height = content_height
+            .max(self.config.dialog_min_height)
+            .min(size.height);
         let dialog_size = Rect::new(
-            size.x + size.width / 3,
-            size.y + size.height / 3,
-            size.width / 3,
-            size.height / 3,
+            size.x + (size.width - width) / 2,
+            size.y + (size.height - height) / 2,
+            width,
+            height,
         );
-
-        let (titel_input_style, desc_input_style) = match self.dialog.selected_input {
-            Input::Titel => (
-                Style::default().fg(Color::Black).bg(Color::LightCyan),
-                Style::default().fg(Color::White).bg(Color::Black),
-            ),
-            Input::Desc => (
-                Style::default().fg(Color::White).bg(Color::Black),
-                Style::default().fg(Color::Black).bg(Color::LightCyan),
-            ),
-        };
+        if dialog_size.width < 3 || dialog_size.height < 3 {
+            return;
+        }
+        let mut constraints = Vec::new();
+        for (_, _, input) in &fields {
+            constraints.push(Constraint::Length(1));
+            constraints.push(match input {
+                Input::Desc if desc_fills => Constraint::Ratio(1, 1),
+                _ => Constraint::Length(1),
+            });
+        }
+        if !matches!(constraints.last(), Some(Constraint::Ratio(_, _))) {
+            constraints.push(Constraint::Ratio(1, 1));
+        }
 
         let dialog_layout = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(1),
-                Constraint::Length(1),
-                Constraint::Length(1),
-                Constraint::Ratio(1, 1),
-            ])
+            .constraints(constraints)
             .split(dialog_size.inner(&Margin {
                 vertical: 1,
                 horizontal: 1,
             }));
 
-        let title_label = Paragraph::new(Text::from("Title"))
-            .style(Style::default().fg(Color::White).bg(Color::Blue))
-            .alignment(Alignment::Left)
-            .wrap(Wrap { trim: true });
-
-        let title = Paragraph::new(Span::raw(self.dialog.input.title.clone()))
-            .style(titel_input_style)
-            .alignment(Alignment::Left)
-            .wrap(Wrap { trim: true });
-
         frame.render_widget(Clear, dialog_size);
         frame.render_widget(dialog_block, dialog_size);
-        frame.render_widget(title_label, dialog_layout[0]);
-        frame.render_widget(title, dialog_layout[1]);
 
-        if self.active_list.is_some() {
-            let desc_label = Paragraph::new(Text::from("Description"))
+        for (index, (label, value, input)) in fields.into_iter().enumerate() {
+            let selected = self.dialog.selected_input == input;
+            let input_style = if selected {
+                Style::default().fg(Color::Black).bg(Color::LightCyan)
+            } else {
+                Style::default().fg(Color::White).bg(Color::Black)
+            };
+
+            // The terminal's cursor sits after the text of the focused field,
+            // on the line the text wraps to.
+            let value_area = dialog_layout[index * 2 + 1];
+            if selected && value_area.width > 0 && value_area.height > 0 {
+                let width = value.width() as u16;
+                let line = (width / value_area.width).min(value_area.height - 1);
+                let column = (width % value_area.width).min(value_area.width - 1);
+                frame.set_cursor(value_area.x + column, value_area.y + line);
+            }
+            let label = if selected {
+                format!("▶ {}", label)
+            } else {
+                label
+            };
+
+            let label = Paragraph::new(Text::from(label))
                 .style(Style::default().fg(Color::White).bg(Color::Blue))
                 .alignment(Alignment::Left)
                 .wrap(Wrap { trim: true });
 
-            let desc = Paragraph::new(Span::raw(self.dialog.input.desc.clone()))
-                .style(desc_input_style)
+            let value = Paragraph::new(Span::raw(value))
+                .style(input_style)
                 .alignment(Alignment::Left)
                 .wrap(Wrap { trim: true });
 
-            frame.render_widget(desc_label, dialog_layout[2]);
-            frame.render_widget(desc, dialog_layout[3]);
+            frame.render_widget(label, dialog_layout[index * 2]);
+            frame.render_widget(value, dialog_layout[index * 2 + 1]);
+        }
+    }
+
+    fn process_rename(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.rename = None;
+            }
+            KeyCode::Enter => {
+                let name = match &self.rename {
+                    Some(name) => name.trim().to_string(),
+                    None => return,
+                };
+                let selected = self.group_list.state.selected();
+                if name.is_empty() {
+                    self.notify("A list needs a name".to_string());
+                    return;
+                }
+                if self
+                    .group_list
+                    .items
+                    .iter()
+                    .enumerate()
+                    .any(|(index, list)| Some(index) != selected && list.name.trim() == name)
+                {
+                    self.notify(format!("There is already a list named \"{}\"", name));
+                    return;
+                }
+                self.rename = None;
+                if let Some(index) = selected {
+                    if let Some(list) = self.group_list.items.get_mut(index) {
+                        if list.name != name {
+                            let id = list.id;
+                            let name = std::mem::replace(&mut list.name, name);
+                            self.record(Operation::Rename { id, name });
+                        }
+                    }
+                }
+            }
+            KeyCode::Char(x) => {
+                if let Some(name) = &mut self.rename {
+                    name.push(x);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(name) = &mut self.rename {
+                    pop_grapheme(name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn process_merge(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc => {
+                self.merge_source = None;
+            }
+            KeyCode::Up => self.navigate(false),
+            KeyCode::Down => self.navigate(true),
+            KeyCode::Enter => {
+                if let (Some(source), Some(target)) =
+                    (self.merge_source.take(), self.group_list.state.selected())
+                {
+                    if source == target {
+                        self.notify("Pick a different list to merge into".to_string());
+                    } else if self.group_list.items[source].list.items.is_empty() {
+                        self.merge_groups(source, target);
+                    } else {
+                        self.confirm = Some(Confirm::MergeGroups { source, target });
+                    }
+                }
+            }
+            _ => {}
         }
     }
 
     pub fn event(&mut self, key: KeyCode, modi: KeyModifiers) {
-        if self.dialog.displayed() && key != KeyCode::Enter {
+        let running = self.running_ids();
+        self.handle_event(key, modi);
+        if self.config.single_timer {
+            self.keep_single_timer(&running);
+        }
+    }
+
+    fn running_ids(&self) -> Vec<u64> {
+        self.group_list
+            .items
+            .iter()
+            .flat_map(|list| list.list.items.iter())
+            .filter(|item| item.running())
+            .map(|item| item.id)
+            .collect()
+    }
+
+    /// Pauses every running item but the one that was just started, if any.
+    fn keep_single_timer(&mut self, running_before: &[u64]) {
+        let started = match self
+            .running_ids()
+            .into_iter()
+            .find(|id| !running_before.contains(id))
+        {
+            Some(started) => started,
+            None => return,
+        };
+        for item in self
+            .group_list
+            .items
+            .iter_mut()
+            .flat_map(|list| list.list.items.iter_mut())
+        {
+            if item.running() && item.id != started {
+                item.paused = true;
+            }
+        }
+    }
+
+    fn handle_event(&mut self, key: KeyCode, modi: KeyModifiers) {
+        self.last_input = Some(Local::now());
+        self.idle_alerted = false;
+        if !self.repairs.is_empty() {
+            self.repairs.clear();
+        } else if self.show_onboarding {
+            self.dismiss_onboarding();
+        } else if self.show_status_history {
+            self.process_status_history(key);
+        } else if self.show_help {
+            self.process_help(key);
+        } else if self.confirm.is_some() {
+            self.process_confirm(key == KeyCode::Char('y'));
+        } else if self.bin_view.is_some() {
+            self.process_bin(key);
+        } else if self.overview.is_some() {
+            self.process_overview(key);
+        } else if self.import.is_some() {
+            self.process_import(key);
+        } else if self.csv_import.is_some() {
+            self.process_csv_import(key);
+        } else if self.tag_view.is_some() {
+            self.process_tags(key);
+        } else if self.scratchpad_view.is_some() {
+            self.process_scratchpad(key);
+        } else if self.show_heatmap {
+            self.process_heatmap(key);
+        } else if self.as_of.is_some() {
+            self.process_as_of(key);
+        } else if self.show_planning {
+            self.process_planning(key);
+        } else if self.review.is_some() {
+            self.process_review(key);
+        } else if self.subtask_focus.is_some() {
+            self.process_subtasks(key);
+        } else if self.timer_focus.is_some() {
+            self.process_timers(key);
+        } else if self.rename.is_some() {
+            self.process_rename(key);
+        } else if self.merge_source.is_some() {
+            self.process_merge(key);
+        } else if self.minimal_ignores(key, modi) {
+            // The minimal view lets only its own keys through.
+        } else if self.dialog.displayed() && key != KeyCode::Enter {
             self.dialog.process_input(key, modi);
-        } else {
+        } else if !self.board_navigate(key, modi) {
             match (key, modi) {
-                (KeyCode::Char('n'), KeyModifiers::CONTROL) => {
-                    if !self.dialog.displayed() {
-                        self.dialog.display(DialogState::New);
-                    }
+                (KeyCode::Char('n'), KeyModifiers::CONTROL) if !self.dialog.displayed() => {
+                    self.dialog.display(DialogState::New);
                 }
-                (KeyCode::Char('e'), KeyModifiers::CONTROL) => {
-                    if !self.dialog.displayed() {
-                        if let Some(item) = self.get_selected_item() {
-                            self.dialog.input = item.clone();
-                            self.dialog.display(DialogState::Edit);
+                (KeyCode::Char('r'), KeyModifiers::CONTROL) if !self.dialog.displayed() => {
+                    self.dialog.display(DialogState::Replace);
+                }
+                (KeyCode::Char('e'), KeyModifiers::CONTROL) if !self.dialog.displayed() => {
+                    if let Some(item) = self.get_selected_unlocked() {
+                        let item = item.clone();
+                        self.dialog.edit(item);
+                    } else if self.active_list.is_none() {
+                        if let Some(index) = self.group_list.state.selected() {
+                            if let Some(list) = self.group_list.items.get(index) {
+                                self.dialog.edit_list(list);
+                            }
                         }
                     }
                 }
                 (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
-                    if let Some(index) = self.active_list {
-                        let list = &mut self.group_list.items.get_mut(index).unwrap().list;
-                        if let Some(index) = list.state.selected() {
-                            list.items.remove(index);
+                    if let Some((list_index, index)) = self.selected_item() {
+                        if !self.selected_locked() {
+                            let list = &mut self.group_list.items.get_mut(list_index).unwrap().list;
+                            let item = list.items.remove(index);
+                            self.bin_item(list_index, index, item.clone());
+                            self.record(Operation::DeleteItem {
+                                list_index,
+                                index,
+                                item,
+                            });
+                        }
+                    } else if self.active_list.is_none() {
+                        if let Some(index) = self.group_list.state.selected() {
+                            if let Some(list) = self.group_list.items.get(index) {
+                                if list.list.items.len() > self.config.confirm_delete_threshold {
+                                    self.confirm = Some(Confirm::DeleteGroup(index));
+                                } else {
+                                    self.delete_group(index);
+                                }
+                            }
                         }
-                    } else if let Some(index) = self.group_list.state.selected() {
-                        self.group_list.items.remove(index);
-                        self.group_list.state.select(None);
                     }
                 }
-                (KeyCode::Char('s'), KeyModifiers::ALT) => {
-                    if let Some(item) = self.get_selected_item() {
-                        if item.start_at.is_some() {
-                            item.start_at = None;
-                            item.end_at = None;
-                            item.duration = 0;
-                        } else {
-                            item.start_at = Some(Local::now());
+                (KeyCode::Char('z'), KeyModifiers::CONTROL) => {
+                    self.undo();
+                }
+                (KeyCode::Char('y'), KeyModifiers::CONTROL) => {
+                    self.redo();
+                }
+                (KeyCode::Char('r'), KeyModifiers::NONE) if self.active_list.is_none() => {
+                    if let Some(index) = self.group_list.state.selected() {
+                        if let Some(list) = self.group_list.items.get(index) {
+                            self.rename = Some(list.name.clone());
                         }
                     }
                 }
-                (KeyCode::Char('d'), KeyModifiers::ALT) => {
+                (KeyCode::Char('m'), KeyModifiers::NONE) if self.active_list.is_none() => {
+                    self.merge_source = self.group_list.state.selected();
+                }
+                (KeyCode::Char('x'), KeyModifiers::NONE) => {
+                    self.cut_selected();
+                }
+                (KeyCode::Char('p'), KeyModifiers::NONE) => {
+                    self.paste();
+                }
+                (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                    self.toggle_pinned();
+                }
+                (KeyCode::Char('>'), _) => {
+                    self.change_progress(PROGRESS_STEP as i16);
+                }
+                (KeyCode::Char('<'), _) => {
+                    self.change_progress(-(PROGRESS_STEP as i16));
+                }
+                (KeyCode::Char('$'), _) => {
+                    if let Some(item) = self.get_selected_task() {
+                        item.non_billable = !item.non_billable;
+                    }
+                }
+                (KeyCode::Char('B'), _) => {
+                    self.toggle_blocked();
+                }
+                (KeyCode::Char('+'), _) => {
+                    self.bump_due(1);
+                }
+                (KeyCode::Char('-'), _) => {
+                    self.bump_due(-1);
+                }
+                (KeyCode::Char('n'), KeyModifiers::ALT) if !self.dialog.displayed() => {
+                    self.dialog.display(DialogState::Capture);
+                }
+                (KeyCode::Char('b'), KeyModifiers::ALT) => {
+                    self.start_timebox();
+                }
+                (KeyCode::Esc, _) if self.timebox.is_some() => {
+                    self.stop_timebox();
+                }
+                (KeyCode::Char('y'), KeyModifiers::ALT) => {
+                    let summary = self.standup_summary();
+                    self.notify(match clipboard::copy(&summary) {
+                        Ok(()) => format!("Copied: {}", summary),
+                        Err(err) => format!("Could not copy to the clipboard: {}", err),
+                    });
+                }
+                (KeyCode::Char('t'), KeyModifiers::ALT) => {
+                    self.toggle_list_timers();
+                }
+                (KeyCode::Char('o'), KeyModifiers::ALT) => {
+                    self.open_link();
+                }
+                (KeyCode::Char('m'), KeyModifiers::ALT)
+                    if !self.dialog.displayed() && self.get_selected_unlocked().is_some() =>
+                {
+                    self.dialog.display(DialogState::Meta);
+                }
+                (KeyCode::Char('x'), KeyModifiers::ALT) if !self.dialog.displayed() => {
+                    if let Some(item) = self.get_selected_task() {
+                        let title = item.title.clone();
+                        self.dialog.input.title = title;
+                        self.dialog.display(DialogState::Split);
+                    }
+                }
+                (KeyCode::Char('e'), KeyModifiers::ALT) => {
+                    self.promote_selected();
+                }
+                (KeyCode::Char('a'), KeyModifiers::ALT)
+                    if !self.dialog.displayed() && self.get_selected_unlocked().is_some() =>
+                {
+                    self.dialog.display(DialogState::Subtask);
+                }
+                (KeyCode::Char('c'), KeyModifiers::ALT) => {
+                    let auto_done = self.config.auto_done_on_subtasks;
+                    if let Some(item) = self.get_selected_unlocked() {
+                        item.set_subtasks_done(true);
+                        if auto_done {
+                            item.sync_done_with_subtasks();
+                        }
+                    }
+                }
+                (KeyCode::Char('u'), KeyModifiers::ALT) => {
+                    let auto_done = self.config.auto_done_on_subtasks;
+                    if let Some(item) = self.get_selected_unlocked() {
+                        item.set_subtasks_done(false);
+                        if auto_done {
+                            item.sync_done_with_subtasks();
+                        }
+                    }
+                }
+                (KeyCode::Char('l'), KeyModifiers::ALT) => {
+                    if let Some(item) = self.get_selected_item() {
+                        item.locked = !item.locked;
+                    }
+                }
+                (KeyCode::Char('h'), KeyModifiers::ALT) => {
+                    self.config.clock_format = self.config.clock_format.next();
+                }
+                (KeyCode::Char('f'), KeyModifiers::ALT) => {
+                    self.minimal = !self.minimal;
+                }
+                (KeyCode::Char('w'), KeyModifiers::ALT) => {
+                    self.switch_workspace();
+                }
+                (KeyCode::Char('b'), KeyModifiers::NONE) => {
+                    self.prune_bin();
+                    self.bin_view = Some(0);
+                }
+                (KeyCode::Char('i'), KeyModifiers::NONE) => {
+                    self.start_import();
+                }
+                (KeyCode::Char('o'), KeyModifiers::CONTROL) => {
+                    self.start_csv_import();
+                }
+                (KeyCode::Char('s'), KeyModifiers::NONE) if self.active_list.is_none() => {
+                    self.copy_list_snippet();
+                }
+                (KeyCode::Char('s'), KeyModifiers::NONE) => {
+                    self.export_snippet();
+                }
+                (KeyCode::Char('i'), KeyModifiers::ALT) if self.active_list.is_none() => {
+                    self.paste_list_snippet();
+                }
+                (KeyCode::Char('i'), KeyModifiers::ALT) => {
+                    self.import_snippet();
+                }
+                (KeyCode::Char('j'), KeyModifiers::NONE) => {
+                    self.jump_to_longest_item();
+                }
+                (KeyCode::Char('j'), KeyModifiers::ALT) => {
+                    self.jump_to_longest_list();
+                }
+                (KeyCode::Char('k'), KeyModifiers::ALT) => {
+                    let capped = self.cap_durations();
+                    self.notify(match capped {
+                        0 => "No duration is longer than its item's span".to_string(),
+                        count => format!("Capped the duration of {} items", count),
+                    });
+                }
+                (KeyCode::Char('v'), KeyModifiers::ALT) => {
+                    self.config.show_list_progress = !self.config.show_list_progress;
+                }
+                (KeyCode::Char('q'), KeyModifiers::ALT) => {
+                    self.config.count_paused = !self.config.count_paused;
+                    self.notify(if self.config.count_paused {
+                        "Paused items keep counting".to_string()
+                    } else {
+                        "Paused items stop counting".to_string()
+                    });
+                }
+                (KeyCode::Char('l'), KeyModifiers::NONE) => {
+                    self.config.show_idle_in_rows = !self.config.show_idle_in_rows;
+                }
+                (KeyCode::Char('g'), KeyModifiers::NONE) => {
+                    self.tag_view = Some(TagView {
+                        row: 0,
+                        rename: None,
+                    });
+                }
+                (KeyCode::Char('w'), KeyModifiers::NONE) => {
+                    self.start_review();
+                }
+                (KeyCode::Char('y'), KeyModifiers::NONE) => {
+                    self.show_heatmap = true;
+                }
+                (KeyCode::Char('n'), KeyModifiers::NONE) => {
+                    self.scratchpad_view = Some(ScratchpadView { scroll_back: 0 });
+                }
+                (KeyCode::Char('v'), KeyModifiers::NONE) => {
+                    self.overview = Some((Overview::Running, 0));
+                }
+                (KeyCode::Char('t'), KeyModifiers::NONE) => {
+                    self.overview = Some((Overview::Today, 0));
+                }
+                (KeyCode::Char('q'), KeyModifiers::NONE) => {
+                    self.overview = Some((Overview::Agenda, 0));
+                }
+                (KeyCode::Char('a'), KeyModifiers::NONE) => {
                     if let Some(item) = self.get_selected_item() {
-                        if item.end_at.is_some() {
-                            item.end_at = None;
+                        let today = Local::today().naive_local();
+                        item.focus_date = if item.focused_today() {
+                            None
                         } else {
-                            item.end_at = Some(Local::now());
+                            Some(today)
+                        };
+                    }
+                }
+                (KeyCode::Char('z'), KeyModifiers::NONE) => {
+                    self.zoomed = !self.zoomed && self.active_list.is_some();
+                }
+                (KeyCode::Char('f'), KeyModifiers::NONE) => {
+                    self.toggle_freeze();
+                }
+                (KeyCode::Char('z'), KeyModifiers::ALT) => {
+                    self.write_snapshot();
+                }
+                (KeyCode::Char('e'), KeyModifiers::NONE) => {
+                    self.notify(match self.export_report() {
+                        Ok(path) => format!("Report written to {}", path),
+                        Err(err) => format!("Could not write report: {}", err),
+                    });
+                }
+                (KeyCode::Char('d'), KeyModifiers::NONE) => {
+                    self.notify(match self.write_daily_note() {
+                        Ok(path) => format!("Daily note written to {}", path),
+                        Err(err) => format!("Could not write daily note: {}", err),
+                    });
+                }
+                (KeyCode::Char('k'), KeyModifiers::NONE) => {
+                    self.board = !self.board;
+                }
+                (KeyCode::Char('g'), KeyModifiers::ALT) => {
+                    self.cycle_group_by();
+                }
+                (KeyCode::Char('c'), KeyModifiers::NONE) => {
+                    self.card_layout = self.card_layout.next();
+                }
+                (KeyCode::Char('o'), KeyModifiers::NONE) => {
+                    self.cycle_sort_mode();
+                }
+                (KeyCode::Char('h'), KeyModifiers::NONE) => {
+                    self.toggle_hide_done();
+                }
+                (KeyCode::Char('u'), KeyModifiers::NONE) => {
+                    self.toggle_show_scheduled();
+                }
+                (KeyCode::Tab, _) => {
+                    self.focus_next_running();
+                }
+                (KeyCode::Char('?'), _) => {
+                    self.show_help = true;
+                    self.help_scroll = 0;
+                }
+                (KeyCode::Char('s'), KeyModifiers::ALT) => {
+                    self.undoable_status(App::start_or_pause);
+                }
+                (KeyCode::Char('r'), KeyModifiers::ALT) => {
+                    if let Some(list_index) = self.active_list {
+                        if let Some(item) = self.get_selected_task() {
+                            let id = item.id;
+                            self.confirm = Some(Confirm::ResetTimer { list_index, id });
                         }
                     }
                 }
+                (KeyCode::Char('d'), KeyModifiers::ALT) => {
+                    self.undoable_status(App::toggle_done);
+                }
+                (KeyCode::Char(' '), _) => {
+                    self.undoable_status(App::cycle_status);
+                }
                 (KeyCode::Char('p'), KeyModifiers::ALT) => {
-                    if let Some(item) = self.get_selected_item() {
-                        item.paused = !item.paused
+                    self.undoable_status(|app| {
+                        if let Some(item) = app.get_selected_task() {
+                            item.toggle_pause();
+                        }
+                    });
+                }
+                (KeyCode::Enter, _) if !self.dialog.displayed() => {
+                    if self.active_list.is_some() && self.config.enter_toggles_timer {
+                        self.undoable_status(App::toggle_timer);
+                    } else if self.config.enter_opens_list {
+                        self.enter_list();
                     }
                 }
                 (KeyCode::Enter, _) => {
-                    if self.dialog.displayed() {
+                    if self.dialog.replacing() {
+                        let count = self.replace_all(
+                            &self.dialog.input.title.clone(),
+                            &self.dialog.input.desc.clone(),
+                            self.dialog.case_sensitive,
+                        );
+                        self.notify(format!("Replaced {} occurrences", count));
+                    } else if self.dialog.adding_subtask() {
+                        let title = self.dialog.input.title.clone();
+                        let auto_done = self.config.auto_done_on_subtasks;
+                        if let Some(item) = self.get_selected_unlocked() {
+                            item.subtasks.push(Subtask { title, done: false });
+                            if auto_done {
+                                item.sync_done_with_subtasks();
+                            }
+                        }
+                    } else if self.dialog.adding_timer() {
+                        let name = self.dialog.input.title.trim().to_string();
+                        if !name.is_empty() {
+                            self.add_timer(name);
+                        }
+                    } else if let Some((list_index, id)) = self.dialog.stop_note() {
+                        let note = self.dialog.input.title.trim().to_string();
+                        if let Some(item) = self.find_item_mut(list_index, id) {
+                            item.stops.push(Stop {
+                                at: item.end_at.unwrap_or_else(Local::now),
+                                duration: item.duration,
+                                note,
+                            });
+                        }
+                    } else if self.dialog.splitting() {
+                        let title = self.dialog.input.title.trim().to_string();
+                        match self.dialog.target_millis() {
+                            Some(split) => self.split_selected(split, title),
+                            None => self
+                                .notify(format!("Invalid split point \"{}\"", self.dialog.target)),
+                        }
+                    } else if self.dialog.editing_meta() {
+                        let key = self.dialog.input.title.trim().to_string();
+                        let value = self.dialog.input.desc.trim().to_string();
+                        if let Some(item) = self.get_selected_unlocked() {
+                            if value.is_empty() {
+                                item.meta.remove(&key);
+                            } else if !key.is_empty() {
+                                item.meta.insert(key, value);
+                            }
+                        }
+                    } else if self.dialog.editing_list() {
+                        self.apply_list_settings();
+                    } else if self.dialog.displayed() {
+                        // An unreadable estimate keeps the one the item had.
+                        match self.dialog.target_millis() {
+                            None if !self.dialog.target.trim().is_empty() => {
+                                self.notify(format!(
+                                    "Could not read estimate \"{}\", try 1h30m or 90m",
+                                    self.dialog.target.trim()
+                                ));
+                            }
+                            target => self.dialog.input.target = target,
+                        }
+                        if self.dialog.due.trim().is_empty() {
+                            self.dialog.input.due_at = None;
+                        } else if let Some(due_at) = self.dialog.due_at(self.config.week_start) {
+                            self.dialog.input.due_at = Some(due_at);
+                        } else {
+                            self.notify(format!(
+                                "Could not read due date \"{}\", try YYYY-MM-DD or \"tomorrow\"",
+                                self.dialog.due.trim()
+                            ));
+                        }
+                        if self.dialog.start.trim().is_empty() {
+                            self.dialog.input.start_date = None;
+                        } else if let Some(date) = self.dialog.start_date(self.config.week_start) {
+                            self.dialog.input.start_date = Some(date);
+                        } else {
+                            self.notify(format!(
+                                "Could not read start date \"{}\", try YYYY-MM-DD or \"monday\"",
+                                self.dialog.start.trim()
+                            ));
+                        }
+                        self.dialog.input.tags = self.dialog.tag_list();
+                        self.dialog.input.link = self.dialog.link();
+                        self.dialog.input.project_code = self.dialog.code();
+                        match self.dialog.alarm_list() {
+                            Some(alarms) => self.dialog.input.alarms = alarms,
+                            None => {
+                                self.notify(format!(
+                                    "Could not read alarms \"{}\", try 15m, 1h",
+                                    self.dialog.alarms.trim()
+                                ));
+                            }
+                        }
+                        self.dialog.input.points = self.dialog.points.parse().ok();
+                        if self.dialog.input.mode == TimerMode::CountDown
+                            && self.dialog.input.target.is_none()
+                        {
+                            self.dialog.input.mode = TimerMode::CountUp;
+                            self.notify(
+                                "A countdown needs a target, counting up instead".to_string(),
+                            );
+                        }
                         if self.dialog.editing() {
-                            let title = self.dialog.input.title.clone();
-                            let desc = self.dialog.input.desc.clone();
+                            let input = self.dialog.input.clone();
+                            let list_index = self.active_list.unwrap_or_default();
                             if let Some(item) = self.get_selected_item() {
-                                item.title = title;
-                                item.desc = desc;
+                                let previous = item.clone();
+                                item.title = input.title;
+                                item.desc = input.desc;
+                                item.target = input.target;
+                                item.over_estimate = item.over_target();
+                                item.auto_stop_at_target = input.auto_stop_at_target;
+                                item.kind = input.kind;
+                                item.mode = input.mode;
+                                item.due_at = input.due_at;
+                                item.start_date = input.start_date;
+                                item.tags = input.tags;
+                                item.link = input.link;
+                                item.project_code = input.project_code;
+                                item.alarms = input.alarms;
+                                item.points = input.points;
+                                self.record(Operation::Edit {
+                                    list_index,
+                                    item: previous,
+                                });
                             }
+                        } else if self.dialog.capturing() {
+                            let mut item = self.dialog.input.clone();
+                            item.id = self.new_id();
+                            if self.config.autostart_on_create && item.is_task() {
+                                item.start();
+                            }
+                            self.capture(item);
                         } else if let Some(index) = self.active_list {
+                            let mut item = self.dialog.input.clone();
+                            item.id = self.new_id();
+                            if self.config.autostart_on_create && item.is_task() {
+                                item.start();
+                            }
+                            let id = item.id;
                             let list = &mut self.group_list.items.get_mut(index).unwrap().list;
-                            list.add(self.dialog.input.clone());
+                            list.add(item);
+                            self.record(Operation::InsertItem { id });
                         } else {
-                            self.group_list.add(GroupList {
-                                name: self.dialog.input.title.to_string(),
-                                list: StatefulList::new(),
-                            });
+                            let list = self.new_list(self.dialog.input.title.to_string());
+                            let id = list.id;
+                            self.group_list.add(list);
+                            self.record(Operation::InsertGroup { id });
+                            if self.config.enter_new_list {
+                                let index = self.group_list.items.len() - 1;
+                                self.group_list.state.select(Some(index));
+                                self.active_list = Some(index);
+                            }
                         }
                     }
                     self.dialog.close_dialog();
+                    self.sort_active_list();
                 }
                 (KeyCode::Up, KeyModifiers::CONTROL) => {
-                    if let Some(index) = self.active_list {
-                        let list = &mut self.group_list.items.get_mut(index).unwrap().list;
-                        list.move_selected_item(ListDirection::Down);
-                    } else {
-                        self.group_list.move_selected_item(ListDirection::Down);
+                    self.move_selected(ListDirection::Down);
+                }
+                (KeyCode::Char('x'), KeyModifiers::CONTROL) => {
+                    self.pick_swap_target();
+                }
+                (KeyCode::Char('b'), KeyModifiers::CONTROL) if self.active_list.is_none() => {
+                    if let Some(list) = self
+                        .group_list
+                        .state
+                        .selected()
+                        .and_then(|index| self.group_list.items.get_mut(index))
+                    {
+                        list.collapsed = !list.collapsed;
                     }
                 }
+                (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
+                    self.open_as_of();
+                }
+                (KeyCode::Char('W'), _) => {
+                    self.show_planning = true;
+                }
+                (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                    self.toggle_all_paused();
+                }
+                (KeyCode::Char('g'), KeyModifiers::CONTROL) => {
+                    self.show_status_history = true;
+                }
+                (KeyCode::Char('f'), KeyModifiers::CONTROL) => {
+                    self.row_time_mode = self.row_time_mode.next();
+                }
+                (KeyCode::Char('k'), KeyModifiers::CONTROL) if !self.dialog.displayed() => {
+                    self.focus_timers();
+                }
                 (KeyCode::Down, KeyModifiers::CONTROL) => {
-                    if let Some(index) = self.active_list {
-                        let list = &mut self.group_list.items.get_mut(index).unwrap().list;
-                        list.move_selected_item(ListDirection::Up);
-                    } else {
-                        self.group_list.move_selected_item(ListDirection::Up);
-                    }
+                    self.move_selected(ListDirection::Up);
+                }
+                (KeyCode::Home, KeyModifiers::CONTROL) => {
+                    self.move_selected_to_end(true);
+                }
+                (KeyCode::End, KeyModifiers::CONTROL) => {
+                    self.move_selected_to_end(false);
                 }
                 (KeyCode::Up, _) => {
-                    if let Some(pos) = self.active_list {
-                        self.group_list.items[pos].list.previous();
-                    } else {
-                        self.group_list.previous();
-                    }
+                    self.navigate(false);
                 }
                 (KeyCode::Down, _) => {
-                    if let Some(pos) = self.active_list {
-                        self.group_list.items[pos].list.next();
-                    } else {
-                        self.group_list.next();
-                    }
+                    self.navigate(true);
+                }
+                (KeyCode::Char('['), KeyModifiers::NONE)
+                | (KeyCode::Left, KeyModifiers::CONTROL) => {
+                    self.switch_list(false);
+                }
+                (KeyCode::Char(']'), KeyModifiers::NONE)
+                | (KeyCode::Right, KeyModifiers::CONTROL) => {
+                    self.switch_list(true);
                 }
                 (KeyCode::Right, _) => {
-                    if self.active_list.is_none() {
-                        self.active_list = self.group_list.state.selected();
+                    let focused = self.active_list.is_some() && self.focus_subtasks();
+                    if !focused {
+                        self.enter_list();
                     }
                 }
-                (KeyCode::Left, _) => {
-                    if let Some(index) = self.active_list {
+                (KeyCode::Left, _) if self.zoomed => {
+                    self.zoomed = false;
+                }
+                (KeyCode::Left, _) => match self.active_list {
+                    Some(index) => {
                         let list = self.group_list.items.get_mut(index).unwrap();
                         list.list.state.select(None);
                         self.active_list = None;
                     }
-                }
+                    None if self.config.left_at_top == LeftAction::Back => self.go_back(),
+                    None if self.config.left_at_top == LeftAction::CollapseDetail => {
+                        self.detail_hidden = !self.detail_hidden;
+                    }
+                    None => {}
+                },
                 _ => {}
             }
         }
     }
 
+    fn status_line(&self) -> String {
+        if self.rename.is_some() {
+            return "Rename · Enter: save · Esc: cancel".to_string();
+        }
+        if let Some(list) = self
+            .merge_source
+            .and_then(|index| self.group_list.items.get(index))
+        {
+            return format!(
+                "Merge \"{}\" into · Up/Down: pick list · Enter: merge · Esc: cancel",
+                list.name
+            );
+        }
+        if self.dialog.displayed() {
+            return "Tab: switch field · Enter: save · Esc: cancel".to_string();
+        }
+        if let Some(message) = self.current_status() {
+            return message.to_string();
+        }
+
+        let mode = match self
+            .active_list
+            .and_then(|index| self.group_list.items.get(index))
+        {
+            Some(list) => format!(
+                "List: {} ({} items, today {})",
+                list.name,
+                list.list.items.len(),
+                format_duration(
+                    self.shown_today_duration(list),
+                    self.config.duration_precision
+                )
+            ),
+            None => format!("Lists ({})", self.group_list.items.len()),
+        };
+        let running = self
+            .group_list
+            .items
+            .iter()
+            .flat_map(|list| list.list.items.iter())
+            .filter(|item| item.running())
+            .count();
+        let long_running = self
+            .group_list
+            .items
+            .iter()
+            .flat_map(|list| list.list.items.iter())
+            .filter(|item| item.long_running(&self.config).is_some())
+            .count();
+        let warning = if long_running > 0 {
+            format!(" ({} long-running)", long_running)
+        } else {
+            String::new()
+        };
+
+        let frozen = if self.all_paused {
+            " (paused)"
+        } else if self.frozen.is_some() {
+            " (frozen)"
+        } else {
+            ""
+        };
+
+        let over_wip = self
+            .group_list
+            .items
+            .iter()
+            .filter(|list| list.over_wip_limit())
+            .map(|list| list.name.as_str())
+            .collect::<Vec<_>>();
+        let wip = if over_wip.is_empty() {
+            String::new()
+        } else {
+            format!(" · Over WIP limit: {}", over_wip.join(", "))
+        };
+
+        format!(
+            "{} · {} running{}{} · Total: {}{} · {} · ?: help",
+            mode,
+            running,
+            warning,
+            wip,
+            format_duration(self.shown_total_duration(), self.config.duration_precision),
+            frozen,
+            self.saved_ago()
+        )
+    }
+
+    fn draw_card<B: Backend>(&self, frame: &mut Frame<B>, item: &Item, area: Rect) {
+        let item = &self.shown(item);
+        let card_block = Block::default()
+            .title(format!(" {} ", item.title.clone()))
+            .borders(Borders::ALL)
+            .style(Style::default().bg(Color::Black));
+
+        let para_box = area.inner(&Margin {
+            vertical: 1,
+            horizontal: 1,
+        });
+
+        let card_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(self.card_layout.constraints().as_ref())
+            .split(para_box);
+
+        // Subtasks get their own list below the description, half of the
+        // space at most unless there is no description.
+        let subtask_height = if item.subtasks.is_empty() {
+            0
+        } else if item.desc.is_empty() {
+            card_layout[0].height
+        } else {
+            (item.subtasks.len() as u16).min(card_layout[0].height / 2)
+        };
+        let desc_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(subtask_height)].as_ref())
+            .split(card_layout[0]);
+
+        let para = Paragraph::new(Text::from(item.desc.clone()))
+            .style(Style::default().fg(Color::White).bg(Color::Black))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(para, desc_layout[0]);
+        if subtask_height > 0 {
+            let mut state = ListState::default();
+            state.select(self.subtask_focus);
+            frame.render_stateful_widget(self.subtask_list(item), desc_layout[1], &mut state);
+        }
+
+        let start_at = if let Some(start_at) = item.start_at {
+            format!(
+                "Started: {}",
+                self.config
+                    .timestamp(start_at, self.config.clock_format.date_time())
+            )
+        } else {
+            "Started: Not started".to_string()
+        };
+
+        let end_at = if let Some(end_at) = item.end_at {
+            format!(
+                "Ended: {}",
+                self.config
+                    .timestamp(end_at, self.config.clock_format.date_time())
+            )
+        } else {
+            "Ended: Not done".to_string()
+        };
+
+        let paused = if item.paused {
+            "Paused"
+        } else if item.start_at.is_some() && item.end_at.is_none() {
+            "In progress"
+        } else {
+            ""
+        };
+
+        let mut info = Text::default();
+        if let Some(remaining) = self.timebox_remaining(item.id) {
+            info.lines.push(Spans::from(vec![Span::styled(
+                format!(
+                    "Timebox: {} left (Esc to stop)",
+                    format_duration(remaining, DurationPrecision::Seconds)
+                ),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )]));
+        }
+        info.lines.push(Spans::from(vec![Span::raw(start_at)]));
+        info.lines.push(Spans::from(vec![Span::raw(end_at)]));
+        match (item.overtime(), item.remaining()) {
+            (Some(overtime), _) => {
+                info.lines.push(Spans::from(vec![Span::styled(
+                    format!(
+                        "Overtime: {}",
+                        format_duration(overtime, self.config.duration_precision)
+                    ),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )]));
+            }
+            (None, Some(remaining)) => {
+                info.lines.push(Spans::from(vec![Span::styled(
+                    format!(
+                        "Remaining: {}",
+                        format_duration(remaining, self.config.duration_precision)
+                    ),
+                    Style::default().fg(Color::Green),
+                )]));
+            }
+            (None, None) => {
+                info.lines.push(Spans::from(vec![Span::raw(
+                    item.formatted_duration(self.config.duration_precision),
+                )]));
+            }
+        }
+        if !item.billable() {
+            info.lines.push(Spans::from(vec![Span::styled(
+                "Not billable",
+                Style::default().fg(Color::Gray),
+            )]));
+        } else if self.config.billing_increment.is_some() {
+            info.lines.push(Spans::from(vec![Span::raw(format!(
+                "Billed: {}",
+                format_duration(
+                    item.billed_duration(self.config.billing_increment),
+                    self.config.duration_precision
+                )
+            ))]));
+        }
+        info.lines.push(Spans::from(vec![Span::raw(paused)]));
+        if !item.done() {
+            info.lines.push(Spans::from(vec![Span::raw(format!(
+                "Last activity: {}",
+                item.idle_label(self.shown_now())
+            ))]));
+        }
+        if let (Some(at), Some(label)) = (item.blocked_at, item.blocked_label(self.shown_now())) {
+            let color = if item.blocked_too_long(&self.config, self.shown_now()) {
+                Color::LightRed
+            } else {
+                Color::Yellow
+            };
+            info.lines.push(Spans::from(vec![Span::styled(
+                format!(
+                    "Blocked since {} ({})",
+                    self.config
+                        .timestamp(at, &format!("%Y-%m-%d {}", self.config.clock_format.time())),
+                    label
+                ),
+                Style::default().fg(color),
+            )]));
+        }
+        if let Some(target) = item.target {
+            let over_target = if item.over_target() {
+                " ⚠ over estimate"
+            } else {
+                ""
+            };
+            info.lines.push(Spans::from(vec![Span::raw(format!(
+                "Target: {}{}",
+                format_duration(target, self.config.duration_precision),
+                over_target
+            ))]));
+            info.lines.push(variance_bar(item.duration, target));
+        }
+        if item.target.is_some() && !item.over_target() && !item.done() {
+            let estimate = match item.days_to_target() {
+                Some(days) if days <= 1 => "At this pace, target within a day".to_string(),
+                Some(days) => format!("At this pace, target in ~{} days", days),
+                None => "No estimate".to_string(),
+            };
+            info.lines.push(Spans::from(vec![Span::raw(estimate)]));
+        }
+        if !item.alarms.is_empty() {
+            let alarms = item
+                .alarms
+                .iter()
+                .map(|alarm| {
+                    let passed = if *alarm <= item.duration { " ✓" } else { "" };
+                    format!(
+                        "{}{}",
+                        format_duration(*alarm, DurationPrecision::Minutes),
+                        passed
+                    )
+                })
+                .collect::<Vec<_>>();
+            info.lines.push(Spans::from(vec![Span::raw(format!(
+                "Alarms: {}",
+                alarms.join(", ")
+            ))]));
+        }
+        info.lines.extend(self.timer_lines(item));
+        for (key, value) in &item.meta {
+            info.lines
+                .push(Spans::from(vec![Span::raw(format!("{}: {}", key, value))]));
+        }
+        for stop in &item.stops {
+            let note = if stop.note.is_empty() {
+                String::new()
+            } else {
+                format!(": {}", stop.note)
+            };
+            info.lines.push(Spans::from(vec![Span::styled(
+                format!(
+                    "Stopped {} at {}{}",
+                    self.config.timestamp(
+                        stop.at,
+                        &format!("%Y-%m-%d {}", self.config.clock_format.time())
+                    ),
+                    format_duration(stop.duration, self.config.duration_precision),
+                    note
+                ),
+                Style::default().fg(Color::Gray),
+            )]));
+        }
+        if let Some(code) = self
+            .active_list
+            .and_then(|index| self.group_list.items.get(index))
+            .and_then(|list| list.project_code_of(item))
+        {
+            info.lines.push(Spans::from(vec![Span::raw(format!(
+                "Project code: {}",
+                code
+            ))]));
+        }
+        if let Some(link) = &item.link {
+            info.lines.push(Spans::from(vec![Span::raw(format!(
+                "Link: {} (Alt+o)",
+                link
+            ))]));
+        }
+        if !item.tags.is_empty() {
+            info.lines.push(Spans::from(vec![Span::raw(format!(
+                "Tags: {}",
+                item.tags.join(", ")
+            ))]));
+        }
+
+        let para = Paragraph::new(info)
+            .style(Style::default().fg(Color::White).bg(Color::Black))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        if let Some((percent, color)) = item.gauge() {
+            let info_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+                .split(card_layout[1]);
+            let gauge = Gauge::default()
+                .gauge_style(Style::default().fg(color).bg(Color::Black))
+                .percent(percent);
+            frame.render_widget(gauge, info_layout[0]);
+            frame.render_widget(para, info_layout[1]);
+        } else {
+            frame.render_widget(para, card_layout[1]);
+        }
+
+        frame.render_widget(card_block, area);
+    }
+
     pub fn draw<B: Backend>(&mut self, frame: &mut Frame<B>) {
         let size = frame.size();
+        if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+            let paragraph = Paragraph::new(Span::raw("Terminal too small"))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+            frame.render_widget(paragraph, size);
+            return;
+        }
+        if self.minimal_shown() {
+            self.draw_minimal(frame);
+            self.draw_paused_banner(frame);
+            return;
+        }
+
+        let screen = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(size);
+
+        let goal = self.config.daily_goal.filter(|minutes| *minutes > 0);
+        let status_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(if goal.is_some() { GOAL_WIDTH } else { 0 }),
+                Constraint::Length(60),
+            ])
+            .split(screen[1]);
+
+        let status_bar = Paragraph::new(Span::raw(self.status_line()))
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left);
+        frame.render_widget(status_bar, status_layout[0]);
+
+        let samples = self.activity.samples();
+        let sparkline = Sparkline::default()
+            .data(&samples)
+            .style(Style::default().fg(Color::LightCyan).bg(Color::Blue));
+        frame.render_widget(sparkline, status_layout[2]);
+
+        if let Some(minutes) = goal {
+            let goal = Duration::minutes(minutes).num_milliseconds();
+            let today = self
+                .group_list
+                .items
+                .iter()
+                .map(|list| self.shown_today_duration(list))
+                .sum::<i64>();
+            let percent = (today * 100 / goal).clamp(0, 100) as u16;
+            let color = match percent {
+                100 => Color::Green,
+                75..=99 => Color::Yellow,
+                _ => Color::Red,
+            };
+            let gauge = Gauge::default()
+                .gauge_style(Style::default().fg(color).bg(Color::Black))
+                .percent(percent)
+                .label(format!(
+                    "{} / {}",
+                    format_duration(today, self.config.duration_precision),
+                    format_duration(goal, self.config.duration_precision)
+                ));
+            frame.render_widget(gauge, status_layout[1]);
+        }
 
         let layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
-            .split(size);
+            .split(screen[0]);
+        let zoomed = self.zoomed && self.active_list.is_some();
+        let collapsed = self.active_list.is_none() && (self.detail_hidden || self.detail_empty());
+        let (groups_area, items_area) = if zoomed {
+            (Rect::default(), screen[0])
+        } else if collapsed {
+            (screen[0], Rect::default())
+        } else if self.config.layout_reversed {
+            (layout[1], layout[0])
+        } else {
+            (layout[0], layout[1])
+        };
 
+        let filter = self.filter;
+        let config = &self.config;
+        let now = self.shown_now();
+        let precision = self.config.duration_precision;
+        let pulse = self.idle() && now.timestamp() % 2 == 0;
         if let Some(index) = self.group_list.state.selected() {
-            if let Some(group_list) = self.group_list.items.get_mut(index) {
+            if let Some(group_list) = self.group_list.items.get(index) {
+                let rows = self.display_rows(index);
+                let mut state = ListState::default();
+                state.select(group_list.list.state.selected().and_then(|selected| {
+                    rows.iter()
+                        .position(|row| matches!(row, Row::Item(index) if *index == selected))
+                }));
+
+                // borders and the highlight symbol take up four columns
+                let divider_width = items_area.width.saturating_sub(4) as usize;
                 let list = List::new(
-                    group_list
-                        .list
-                        .items
-                        .clone()
-                        .into_iter()
-                        .map(|item| {
-                            let style = if item.done() {
+                    rows.iter()
+                        .map(|row| {
+                            let item = match row {
+                                Row::Header(header) => {
+                                    return ListItem::new(Span::styled(
+                                        header.clone(),
+                                        Style::default()
+                                            .fg(Color::Cyan)
+                                            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                                    ));
+                                }
+                                Row::Item(index) => group_list.list.items[*index].clone(),
+                            };
+                            if !item.is_task() {
+                                return ListItem::new(Span::styled(
+                                    format!("{:^width$}", item.title, width = divider_width),
+                                    Style::default()
+                                        .fg(Color::DarkGray)
+                                        .add_modifier(Modifier::DIM),
+                                ));
+                            }
+
+                            let style = if item.done() && config.dim_done {
+                                Style::default()
+                                    .fg(Color::Green)
+                                    .add_modifier(Modifier::DIM | Modifier::CROSSED_OUT)
+                            } else if item.done() {
                                 Style::default().fg(Color::Green)
                             } else if item.paused {
                                 Style::default().fg(Color::Blue)
+                            } else if item.over_target() {
+                                Style::default().fg(Color::Red)
+                            } else if let Some(level) = item.long_running(config) {
+                                match level {
+                                    LongRunning::Alert => {
+                                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                                    }
+                                    LongRunning::Warning => Style::default().fg(Color::LightRed),
+                                }
                             } else if item.started() {
                                 Style::default().fg(Color::Yellow)
                             } else {
                                 Style::default().fg(Color::White)
                             };
+                            let style = if pulse && item.running() {
+                                style.add_modifier(Modifier::REVERSED)
+                            } else {
+                                style
+                            };
+
+                            let pin = if item.pinned { "* " } else { "" };
+                            let lock = if item.locked { "[L] " } else { "" };
+                            let focus = if item.focused_today() { "» " } else { "" };
+                            let subtasks = if item.subtasks.is_empty() {
+                                String::new()
+                            } else {
+                                format!(" ({}/{})", item.subtasks_done(), item.subtasks.len())
+                            };
+                            let points = match item.points {
+                                Some(points) => format!(" · {} pts", points),
+                                None => String::new(),
+                            };
+                            let points = if item.progress > 0 {
+                                format!("{} · {}%", points, item.progress)
+                            } else {
+                                points
+                            };
+                            let title = if self.config.truncate_titles {
+                                let used = pin.width()
+                                    + lock.width()
+                                    + focus.width()
+                                    + subtasks.width()
+                                    + points.width();
+                                truncate_to_width(&item.title, divider_width.saturating_sub(used))
+                            } else {
+                                item.title.clone()
+                            };
+                            let mut spans = vec![Span::styled(
+                                format!("{}{}{}{}{}{}", pin, lock, focus, title, subtasks, points),
+                                style,
+                            )];
+                            if let Some(time) = self.row_time_mode.label(&item, precision) {
+                                spans.push(Span::styled(
+                                    time,
+                                    Style::default().fg(Color::LightCyan),
+                                ));
+                            }
+                            if let (Some(due_at), false) = (item.due_at, item.done()) {
+                                let remaining = due_at.signed_duration_since(now);
+                                if remaining < Duration::zero() {
+                                    spans.push(Span::styled(
+                                        format!(
+                                            " (overdue {})",
+                                            format_duration(
+                                                -remaining.num_milliseconds(),
+                                                precision
+                                            )
+                                        ),
+                                        Style::default().fg(Color::Red),
+                                    ));
+                                } else {
+                                    spans.push(Span::styled(
+                                        format!(
+                                            " (due in {})",
+                                            format_duration(
+                                                remaining.num_milliseconds(),
+                                                precision
+                                            )
+                                        ),
+                                        Style::default().fg(Color::Gray),
+                                    ));
+                                }
+                            }
+                            if let (Some(date), true) = (item.start_date, item.scheduled()) {
+                                spans.push(Span::styled(
+                                    format!(" (starts {})", date.format("%Y-%m-%d")),
+                                    Style::default().fg(Color::DarkGray),
+                                ));
+                            }
+                            if item.over_target() && !item.done() {
+                                spans.push(Span::styled(
+                                    " ⚠ over estimate",
+                                    Style::default().fg(Color::LightRed),
+                                ));
+                            }
+                            if let Some(label) = item.blocked_label(now) {
+                                let color = if item.blocked_too_long(config, now) {
+                                    Color::LightRed
+                                } else {
+                                    Color::Yellow
+                                };
+                                spans.push(Span::styled(
+                                    format!(" ({})", label),
+                                    Style::default().fg(color),
+                                ));
+                            }
+                            if config.show_idle_in_rows && item.is_task() && !item.done() {
+                                spans.push(Span::styled(
+                                    format!(" ({})", item.idle_label(now)),
+                                    Style::default().fg(Color::DarkGray),
+                                ));
+                            }
+
+                            let mut width: usize =
+                                spans.iter().map(|span| span.content.chars().count()).sum();
+                            for tag in &item.tags {
+                                let chip = format!(" [{}]", tag);
+                                if width + chip.chars().count() > divider_width {
+                                    spans
+                                        .push(Span::styled(" …", Style::default().fg(Color::Gray)));
+                                    break;
+                                }
+                                width += chip.chars().count();
+                                spans.push(Span::styled(
+                                    chip,
+                                    Style::default().fg(Color::Black).bg(tag_color(tag)),
+                                ));
+                            }
 
-                            ListItem::new(Span::styled(item.title, style))
+                            ListItem::new(Spans::from(spans))
                         })
                         .collect::<Vec<_>>(),
                 );
 
+                let hidden = if filter.hide_done {
+                    "(done hidden) "
+                } else {
+                    ""
+                };
+                let scheduled = group_list
+                    .list
+                    .items
+                    .iter()
+                    .filter(|item| item.scheduled())
+                    .count();
+                let hidden = match (filter.show_scheduled, scheduled) {
+                    (_, 0) => hidden.to_string(),
+                    (true, _) => format!("{}(scheduled shown) ", hidden),
+                    (false, count) => format!("{}({} scheduled) ", hidden, count),
+                };
                 let block = Block::default()
-                    .title(format!(" {} ", group_list.name.clone()))
+                    .title(format!(
+                        " {} {}{}{}",
+                        group_list.name.clone(),
+                        group_list.sort_mode.label(),
+                        self.group_by.label(),
+                        hidden
+                    ))
                     .borders(Borders::ALL)
                     .style(Style::default().bg(Color::Black));
 
@@ -455,81 +4754,35 @@ impl<'a> App {
                     .highlight_style(Style::default().add_modifier(Modifier::BOLD))
                     .highlight_symbol("> ");
 
-                if let Some(index) = group_list.list.state.selected() {
-                    if let Some(item) = group_list.list.items.get(index) {
+                // The right pane shows the selected group's items. When a task is
+                // selected its card takes the lower half, below the same list.
+                let selected = group_list
+                    .list
+                    .state
+                    .selected()
+                    .and_then(|index| group_list.list.items.get(index))
+                    .filter(|item| item.is_task() && filter.matches(item));
+                let list_area = match selected {
+                    Some(item) => {
                         let item_list_layout = Layout::default()
                             .direction(Direction::Vertical)
                             .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
-                            .split(layout[1]);
-
-                        let dialog_block = Block::default()
-                            .title(format!(" {} ", item.title.clone()))
-                            .borders(Borders::ALL)
-                            .style(Style::default().bg(Color::Black));
-
-                        let para_box = item_list_layout[1].inner(&Margin {
-                            vertical: 1,
-                            horizontal: 1,
-                        });
-
-                        let card_layout = Layout::default()
-                            .direction(Direction::Vertical)
-                            .constraints([Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)])
-                            .split(para_box);
-
-                        let para = Paragraph::new(Span::raw(item.desc.clone()))
-                            .style(Style::default().fg(Color::White).bg(Color::Black))
-                            .alignment(Alignment::Left)
-                            .wrap(Wrap { trim: true });
-
-                        frame.render_widget(para, card_layout[0]);
-
-                        let start_at = if let Some(start_at) = item.start_at {
-                            format!("Started: {}", start_at.to_rfc2822())
-                        } else {
-                            "Started: Not started".to_string()
-                        };
-
-                        let end_at = if let Some(end_at) = item.end_at {
-                            format!("Ended: {}", end_at.to_rfc2822())
-                        } else {
-                            "Ended: Not done".to_string()
-                        };
-
-                        let paused = if item.paused {
-                            "Paused"
-                        } else if item.start_at.is_some() && item.end_at.is_none() {
-                            "In progress"
-                        } else {
-                            ""
-                        };
-
-                        let mut info = Text::default();
-                        info.lines.push(Spans::from(vec![Span::raw(start_at)]));
-                        info.lines.push(Spans::from(vec![Span::raw(end_at)]));
-                        info.lines
-                            .push(Spans::from(vec![Span::raw(item.formatted_duration())]));
-                        info.lines.push(Spans::from(vec![Span::raw(paused)]));
-
-                        let para = Paragraph::new(info)
-                            .style(Style::default().fg(Color::White).bg(Color::Black))
-                            .alignment(Alignment::Left)
-                            .wrap(Wrap { trim: true });
-
-                        frame.render_widget(para, card_layout[1]);
-
-                        frame.render_widget(dialog_block, item_list_layout[1]);
-
-                        frame.render_stateful_widget(
-                            list,
-                            item_list_layout[0],
-                            &mut group_list.list.state,
-                        );
-                    } else {
-                        frame.render_stateful_widget(list, layout[1], &mut group_list.list.state);
+                            .split(items_area);
+                        self.draw_card(frame, item, item_list_layout[1]);
+                        item_list_layout[0]
                     }
+                    None => items_area,
+                };
+
+                if self.board {
+                    self.draw_board(frame, group_list, list_area);
                 } else {
-                    frame.render_stateful_widget(list, layout[1], &mut group_list.list.state);
+                    let selected = state.selected();
+                    frame.render_stateful_widget(list, list_area, &mut state);
+                    draw_scroll_hints(frame, list_area, rows.len(), selected);
+                }
+                if group_list.list.items.is_empty() {
+                    draw_empty_hint(frame, list_area, &self.config.empty_list_message);
                 }
             }
         }
@@ -539,7 +4792,64 @@ impl<'a> App {
                 .items
                 .clone()
                 .into_iter()
-                .map(|gl| ListItem::new(Span::raw(gl.name)))
+                .enumerate()
+                .map(|(index, gl)| match &self.rename {
+                    Some(name) if self.group_list.state.selected() == Some(index) => {
+                        ListItem::new(Span::styled(
+                            format!("{}_", name),
+                            Style::default().fg(Color::Black).bg(Color::LightCyan),
+                        ))
+                    }
+                    _ => {
+                        let label = match &gl.icon {
+                            Some(icon) => format!("{} {}", icon, gl.name),
+                            None => gl.name.clone(),
+                        };
+                        let color = gl.color.as_deref().and_then(parse_color);
+                        // Collapsed lists skip the checks that walk their items.
+                        let name = if !gl.collapsed && gl.over_wip_limit() {
+                            Span::styled(label, Style::default().fg(Color::Red))
+                        } else if !gl.collapsed && gl.stale(&self.config) {
+                            Span::styled(label, Style::default().fg(Color::DarkGray))
+                        } else if let Some(color) = color {
+                            Span::styled(label, Style::default().fg(color))
+                        } else {
+                            Span::raw(label)
+                        };
+                        let mut spans = vec![name];
+                        if gl.collapsed {
+                            spans.push(Span::styled(" …", Style::default().fg(Color::Gray)));
+                            return ListItem::new(Spans::from(spans));
+                        }
+                        let (done, total) = gl.done_count();
+                        if self.config.show_list_progress && total > 0 {
+                            spans.push(Span::styled(
+                                format!(" {}", progress_bar(done, total)),
+                                Style::default().fg(Color::Green),
+                            ));
+                        }
+                        if let Some(rounded) = gl.rounded_duration() {
+                            spans.push(Span::styled(
+                                format!("  {}", format_hours(rounded)),
+                                Style::default().fg(Color::LightCyan),
+                            ));
+                        }
+                        let points = gl.total_points();
+                        if points > 0 {
+                            spans.push(Span::styled(
+                                format!("  {} pts", points),
+                                Style::default().fg(Color::Gray),
+                            ));
+                        }
+                        if let Some(cost) = gl.formatted_cost(self.config.billing_increment) {
+                            spans.push(Span::styled(
+                                format!("  {}", cost),
+                                Style::default().fg(Color::Green),
+                            ));
+                        }
+                        ListItem::new(Spans::from(spans))
+                    }
+                })
                 .collect::<Vec<_>>(),
         );
 
@@ -560,9 +4870,738 @@ impl<'a> App {
             .highlight_style(Style::default().add_modifier(Modifier::BOLD))
             .highlight_symbol("> ");
 
-        frame.render_stateful_widget(list, layout[0], &mut self.group_list.state);
+        if !zoomed {
+            frame.render_stateful_widget(list, groups_area, &mut self.group_list.state);
+            if self.group_list.items.is_empty() {
+                draw_empty_hint(frame, groups_area, &self.config.empty_lists_message);
+            }
+        }
         if self.dialog.displayed() {
             self.show_dialog(frame);
         }
+        if self.bin_view.is_some() {
+            self.draw_bin(frame);
+        }
+        self.draw_paused_banner(frame);
+        self.draw_overview(frame);
+        self.draw_import(frame);
+        self.draw_csv_import(frame);
+        self.draw_tags(frame);
+        self.draw_scratchpad(frame);
+        self.draw_review(frame);
+        self.draw_heatmap(frame);
+        self.draw_as_of(frame);
+        self.draw_planning(frame);
+        self.draw_confirm(frame);
+        self.draw_repairs(frame);
+        self.draw_status_history(frame);
+        self.draw_onboarding(frame);
+        if self.show_help {
+            self.draw_help(frame);
+        }
+    }
+}
+
+/// An app with the "Work" list open, holding items with the given titles.
+#[cfg(test)]
+pub(crate) fn app_with_items(titles: &[&str]) -> App {
+    let mut app = App::new("Test".to_string());
+    let mut list = app.new_list("Work".to_string());
+    for title in titles {
+        list.list.add(Item {
+            id: app.new_id(),
+            title: title.to_string(),
+            ..Item::default()
+        });
+    }
+    app.group_list.add(list);
+    app.group_list.state.select(Some(0));
+    app.active_list = Some(0);
+    app
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: i64 = 60_000;
+
+    fn items(app: &App) -> &[Item] {
+        &app.group_list.items[0].list.items
+    }
+
+    fn app_with_target(auto_stop: bool) -> App {
+        let mut app = app_with_items(&["Task"]);
+        let item = &mut app.group_list.items[0].list.items[0];
+        item.target = Some(2 * MINUTE);
+        item.auto_stop_at_target = auto_stop;
+        item.start();
+        app
+    }
+
+    #[test]
+    fn auto_stop_ends_the_item_at_its_target() {
+        let mut app = app_with_target(true);
+        app.add_time(std::time::Duration::from_secs(90));
+        assert!(!items(&app)[0].done());
+        app.add_time(std::time::Duration::from_secs(90));
+        assert!(items(&app)[0].done());
+        assert!(items(&app)[0].over_estimate);
+        app.add_time(std::time::Duration::from_secs(90));
+        assert_eq!(items(&app)[0].duration, 3 * MINUTE);
+    }
+
+    #[test]
+    fn without_auto_stop_the_item_keeps_counting() {
+        let mut app = app_with_target(false);
+        app.add_time(std::time::Duration::from_secs(90));
+        assert!(!items(&app)[0].over_estimate);
+        app.add_time(std::time::Duration::from_secs(90));
+        assert!(items(&app)[0].over_estimate);
+        assert!(!items(&app)[0].done());
+        app.add_time(std::time::Duration::from_secs(90));
+        assert_eq!(items(&app)[0].duration, 4 * MINUTE + MINUTE / 2);
+    }
+
+    #[test]
+    fn sorting_breaks_ties_by_id() {
+        let mut app = app_with_items(&["Same", "same", "Other"]);
+        let list = &mut app.group_list.items[0];
+        list.list.items.swap(0, 1);
+        list.sort_mode = SortMode::Title;
+        list.apply_sort();
+        let ids = |list: &GroupList<Item>| {
+            list.list
+                .items
+                .iter()
+                .map(|item| item.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(list), vec![4, 2, 3]);
+
+        list.list.items.reverse();
+        list.sort_mode = SortMode::Duration;
+        list.apply_sort();
+        assert_eq!(ids(list), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn lists_and_items_wrap_alike() {
+        for wrap in [true, false].iter() {
+            let mut app = app_with_items(&["First", "Second"]);
+            app.group_list.add(GroupList::new("Home".to_string()));
+            app.config.wrap_navigation = *wrap;
+            let expected = if *wrap { Some(0) } else { Some(1) };
+
+            app.group_list.items[0].list.state.select(Some(1));
+            app.event(KeyCode::Down, KeyModifiers::NONE);
+            assert_eq!(app.group_list.items[0].list.state.selected(), expected);
+
+            app.active_list = None;
+            app.group_list.state.select(Some(1));
+            app.event(KeyCode::Down, KeyModifiers::NONE);
+            assert_eq!(app.group_list.state.selected(), expected);
+        }
+    }
+
+    fn app_for_skipping() -> App {
+        let mut app = app_with_items(&["Open", "Done", "Divider", "Also open"]);
+        let items = &mut app.group_list.items[0].list.items;
+        items[1].start();
+        items[1].end_at = Some(Local::now());
+        items[2].kind = ItemKind::Divider;
+        app.config.skip_done_in_nav = true;
+        app.config.skip_dividers = true;
+        app
+    }
+
+    fn selected(app: &App) -> Option<usize> {
+        app.group_list.items[0].list.state.selected()
+    }
+
+    #[test]
+    fn navigation_skips_done_items_and_dividers() {
+        let mut app = app_for_skipping();
+        app.group_list.items[0].list.state.select(Some(0));
+        app.event(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(selected(&app), Some(3));
+        app.event(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(selected(&app), Some(0));
+        app.event(KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(selected(&app), Some(3));
+
+        app.config.skip_dividers = false;
+        app.event(KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(selected(&app), Some(2));
+    }
+
+    #[test]
+    fn navigation_stays_put_when_everything_is_skipped() {
+        let mut app = app_for_skipping();
+        for item in &mut app.group_list.items[0].list.items {
+            item.kind = ItemKind::Divider;
+        }
+        app.group_list.items[0].list.state.select(Some(1));
+        app.event(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(selected(&app), Some(1));
+        app.event(KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(selected(&app), Some(1));
+    }
+
+    #[test]
+    fn undoing_a_split_puts_the_time_back_at_once() {
+        let mut app = app_with_items(&["Meeting", "Next"]);
+        app.group_list.items[0].list.items[0].duration = 90 * MINUTE;
+        app.group_list.items[0].list.state.select(Some(0));
+        app.split_selected(60 * MINUTE, "Follow-up".to_string());
+        let titles = |app: &App| {
+            items(app)
+                .iter()
+                .map(|item| (item.title.clone(), item.duration))
+                .collect::<Vec<_>>()
+        };
+        let split = vec![
+            ("Meeting".to_string(), 60 * MINUTE),
+            ("Follow-up".to_string(), 30 * MINUTE),
+            ("Next".to_string(), 0),
+        ];
+        assert_eq!(titles(&app), split);
+
+        app.undo();
+        assert_eq!(
+            titles(&app),
+            vec![
+                ("Meeting".to_string(), 90 * MINUTE),
+                ("Next".to_string(), 0)
+            ]
+        );
+        app.redo();
+        assert_eq!(titles(&app), split);
+    }
+
+    #[test]
+    fn detail_collapses_without_items_to_show() {
+        let mut app = app_with_items(&["Task"]);
+        app.config.collapse_empty_detail = true;
+        app.active_list = None;
+        assert!(!app.detail_empty());
+
+        app.group_list.add(GroupList::new("Empty".to_string()));
+        app.group_list.state.select(Some(1));
+        assert!(app.detail_empty());
+        app.group_list.state.select(None);
+        assert!(app.detail_empty());
+
+        app.config.collapse_empty_detail = false;
+        assert!(!app.detail_empty());
+    }
+
+    #[test]
+    fn capping_limits_started_items_and_keeps_unstarted_ones() {
+        let mut app = app_with_items(&["Imported", "Overcounted", "Fine"]);
+        let now = Local::now();
+        {
+            let items = &mut app.group_list.items[0].list.items;
+            items[0].duration = 90 * MINUTE;
+            items[0].tracked_today = 100 * MINUTE;
+            items[1].start_at = Some(now - Duration::minutes(10));
+            items[1].duration = 60 * MINUTE;
+            items[2].start_at = Some(now - Duration::minutes(60));
+            items[2].end_at = Some(now - Duration::minutes(30));
+            items[2].duration = 20 * MINUTE;
+        }
+        assert_eq!(app.cap_durations(), 2);
+        assert_eq!(items(&app)[0].duration, 90 * MINUTE);
+        assert_eq!(items(&app)[0].tracked_today, 90 * MINUTE);
+        assert!((10 * MINUTE..11 * MINUTE).contains(&items(&app)[1].duration));
+        assert_eq!(items(&app)[2].duration, 20 * MINUTE);
+    }
+
+    #[test]
+    fn repeated_starts_keep_the_first_start() {
+        let mut item = Item::default();
+        item.start();
+        let started = item.start_at;
+        item.duration = MINUTE;
+        for _ in 0..5 {
+            item.start();
+        }
+        assert_eq!(item.start_at, started);
+        assert_eq!(item.duration, MINUTE);
+        assert!(item.running());
+    }
+
+    #[test]
+    fn rapid_pause_toggles_alternate() {
+        let mut item = Item::default();
+        item.toggle_pause();
+        assert!(!item.paused);
+        item.start();
+        for presses in 1..=5 {
+            item.toggle_pause();
+            assert_eq!(item.paused, presses % 2 == 1);
+        }
+        item.end_at = Some(Local::now());
+        item.toggle_pause();
+        assert!(item.paused);
+        assert!(!item.running());
+    }
+
+    #[test]
+    fn repeated_resets_leave_a_fresh_item() {
+        let mut item = Item::default();
+        item.start();
+        item.paused = true;
+        item.duration = MINUTE;
+        item.track_today(MINUTE);
+        for _ in 0..3 {
+            item.reset();
+            assert!(!item.started() && !item.paused && !item.done());
+            assert_eq!(item.duration, 0);
+            assert!(item.daily_log.is_empty());
+        }
+    }
+
+    #[test]
+    fn rapid_alt_s_toggles_pause_without_losing_time() {
+        let mut app = app_with_items(&["Task"]);
+        app.group_list.items[0].list.state.select(Some(0));
+        app.event(KeyCode::Char('s'), KeyModifiers::ALT);
+        app.group_list.items[0].list.items[0].duration = 5 * MINUTE;
+        let started = items(&app)[0].start_at;
+        for presses in 1..=4 {
+            app.event(KeyCode::Char('s'), KeyModifiers::ALT);
+            assert_eq!(items(&app)[0].paused, presses % 2 == 1);
+        }
+        assert!(items(&app)[0].running());
+        assert_eq!(items(&app)[0].start_at, started);
+        assert_eq!(items(&app)[0].duration, 5 * MINUTE);
+    }
+
+    fn titles(app: &App) -> Vec<&str> {
+        items(app).iter().map(|item| item.title.as_str()).collect()
+    }
+
+    fn cycle_sort(app: &mut App, times: usize) {
+        for _ in 0..times {
+            app.event(KeyCode::Char('o'), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn manual_order_survives_sorting() {
+        let mut app = app_with_items(&["c", "a", "b"]);
+        app.group_list.items[0].list.state.select(Some(2));
+        app.event(KeyCode::Up, KeyModifiers::CONTROL);
+        let manual = titles(&app)
+            .iter()
+            .map(|title| title.to_string())
+            .collect::<Vec<_>>();
+        assert_ne!(manual, vec!["c", "a", "b"]);
+
+        cycle_sort(&mut app, 1);
+        assert!(app.group_list.items[0].sort_mode == SortMode::Title);
+        assert_eq!(titles(&app), vec!["a", "b", "c"]);
+        cycle_sort(&mut app, 4);
+        assert!(app.group_list.items[0].sort_mode == SortMode::Manual);
+        assert_eq!(titles(&app), manual);
+    }
+
+    #[test]
+    fn items_added_while_sorted_go_last_in_manual_order() {
+        let mut app = app_with_items(&["c", "b"]);
+        cycle_sort(&mut app, 1);
+        app.event(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        app.event(KeyCode::Char('a'), KeyModifiers::NONE);
+        app.event(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(titles(&app), vec!["a", "b", "c"]);
+        cycle_sort(&mut app, 4);
+        assert_eq!(titles(&app), vec!["c", "b", "a"]);
+    }
+
+    fn countdown(duration: i64) -> Item {
+        Item {
+            mode: TimerMode::CountDown,
+            target: Some(10 * MINUTE),
+            duration,
+            ..Item::default()
+        }
+    }
+
+    #[test]
+    fn countdown_at_its_target_is_not_overtime() {
+        let item = countdown(10 * MINUTE);
+        assert_eq!(item.remaining(), Some(0));
+        assert_eq!(item.overtime(), None);
+        assert_eq!(item.gauge(), Some((100, Color::Cyan)));
+    }
+
+    #[test]
+    fn countdown_past_its_target_shows_overtime_in_red() {
+        let item = countdown(10 * MINUTE + 1_000);
+        assert_eq!(item.overtime(), Some(1_000));
+        assert_eq!(item.gauge(), Some((100, Color::Red)));
+        let item = countdown(13 * MINUTE);
+        assert_eq!(item.overtime(), Some(3 * MINUTE));
+        assert_eq!(item.gauge(), Some((100, Color::Red)));
+    }
+
+    #[test]
+    fn countdown_below_its_target_fills_the_gauge() {
+        assert_eq!(countdown(MINUTE).overtime(), None);
+        assert_eq!(countdown(MINUTE).gauge(), Some((10, Color::Cyan)));
+        let count_up = Item {
+            target: Some(MINUTE),
+            duration: 2 * MINUTE,
+            ..Item::default()
+        };
+        assert_eq!(count_up.overtime(), None);
+        assert_eq!(count_up.gauge(), None);
+    }
+
+    fn app_with_paused_item(count_paused: bool) -> App {
+        let mut app = app_with_items(&["Task"]);
+        app.config.count_paused = count_paused;
+        let item = &mut app.group_list.items[0].list.items[0];
+        item.start();
+        item.toggle_pause();
+        app
+    }
+
+    fn tick(app: &mut App, times: usize) {
+        for _ in 0..times {
+            app.add_time(std::time::Duration::from_secs(60));
+        }
+    }
+
+    #[test]
+    fn paused_items_do_not_count_by_default() {
+        let mut app = app_with_paused_item(false);
+        tick(&mut app, 3);
+        assert_eq!(items(&app)[0].duration, 0);
+        app.group_list.items[0].list.items[0].toggle_pause();
+        tick(&mut app, 2);
+        assert_eq!(items(&app)[0].duration, 2 * MINUTE);
+    }
+
+    #[test]
+    fn count_paused_keeps_logging_paused_items() {
+        let mut app = app_with_paused_item(true);
+        tick(&mut app, 3);
+        assert_eq!(items(&app)[0].duration, 3 * MINUTE);
+        assert!(items(&app)[0].paused);
+        assert!(!items(&app)[0].running());
+
+        app.group_list.items[0].list.items[0].end_at = Some(Local::now());
+        tick(&mut app, 2);
+        assert_eq!(items(&app)[0].duration, 3 * MINUTE);
+    }
+
+    #[test]
+    fn alt_q_toggles_count_paused() {
+        let mut app = app_with_paused_item(false);
+        app.event(KeyCode::Char('q'), KeyModifiers::ALT);
+        tick(&mut app, 1);
+        assert_eq!(items(&app)[0].duration, MINUTE);
+        app.event(KeyCode::Char('q'), KeyModifiers::ALT);
+        tick(&mut app, 1);
+        assert_eq!(items(&app)[0].duration, MINUTE);
+    }
+
+    #[test]
+    fn single_timer_pauses_the_other_timers() {
+        let mut app = app_with_items(&["First", "Second"]);
+        app.config.single_timer = true;
+        app.group_list.items[0].list.state.select(Some(0));
+        app.event(KeyCode::Char('s'), KeyModifiers::ALT);
+        app.group_list.items[0].list.state.select(Some(1));
+        app.event(KeyCode::Char('s'), KeyModifiers::ALT);
+        assert!(items(&app)[0].started() && items(&app)[0].paused);
+        assert!(items(&app)[1].running());
+    }
+
+    #[test]
+    fn timers_run_side_by_side_without_single_timer() {
+        let mut app = app_with_items(&["First", "Second"]);
+        app.group_list.items[0].list.state.select(Some(0));
+        app.event(KeyCode::Char('s'), KeyModifiers::ALT);
+        app.group_list.items[0].list.state.select(Some(1));
+        app.event(KeyCode::Char('s'), KeyModifiers::ALT);
+        assert!(items(&app)[0].running());
+        assert!(items(&app)[1].running());
+    }
+
+    #[test]
+    fn ticks_are_logged_on_their_day() {
+        let mut item = Item::default();
+        item.track_today(60_000);
+        item.track_today(30_000);
+        assert_eq!(
+            item.daily_log.get(&Local::today().naive_local()),
+            Some(&90_000)
+        );
+        item.reset();
+        assert!(item.daily_log.is_empty());
+    }
+
+    #[test]
+    fn rounding_halves_go_up() {
+        assert_eq!(
+            round_to_nearest(7 * MINUTE + 30_000, 15 * MINUTE),
+            15 * MINUTE
+        );
+        assert_eq!(round_to_nearest(7 * MINUTE + 29_999, 15 * MINUTE), 0);
+        assert_eq!(
+            round_to_nearest(22 * MINUTE + 30_000, 15 * MINUTE),
+            30 * MINUTE
+        );
+        assert_eq!(round_to_nearest(15 * MINUTE, 15 * MINUTE), 15 * MINUTE);
+    }
+
+    #[test]
+    fn rounding_zero_and_no_increment() {
+        assert_eq!(round_to_nearest(0, 15 * MINUTE), 0);
+        assert_eq!(round_to_nearest(7 * MINUTE, 0), 7 * MINUTE);
+        assert_eq!(round_to_nearest(7 * MINUTE, -15 * MINUTE), 7 * MINUTE);
+    }
+
+    #[test]
+    fn rounding_negative_times() {
+        assert_eq!(round_to_nearest(-10 * MINUTE, 15 * MINUTE), -15 * MINUTE);
+        assert_eq!(round_to_nearest(-5 * MINUTE, 15 * MINUTE), 0);
+        // The half goes up, towards zero for negative times.
+        assert_eq!(round_to_nearest(-7 * MINUTE - 30_000, 15 * MINUTE), 0);
+    }
+
+    #[test]
+    fn hours_are_formatted_without_trailing_zeros() {
+        assert_eq!(format_hours(0), "0h");
+        assert_eq!(format_hours(135 * MINUTE), "2.25h");
+        assert_eq!(format_hours(150 * MINUTE), "2.5h");
+        assert_eq!(format_hours(600 * MINUTE), "10h");
+        assert_eq!(format_hours(-30 * MINUTE), "-0.5h");
+    }
+
+    #[test]
+    fn iso8601_durations() {
+        assert_eq!(format_iso8601(0), "PT0S");
+        assert_eq!(format_iso8601(999), "PT0S");
+        assert_eq!(format_iso8601(-MINUTE), "PT0S");
+        assert_eq!(format_iso8601(45_000), "PT45S");
+        assert_eq!(format_iso8601(90 * MINUTE), "PT1H30M");
+        assert_eq!(format_iso8601(120 * MINUTE + 5_000), "PT2H5S");
+        assert_eq!(
+            format_iso8601(25 * 60 * MINUTE + MINUTE + 1_000),
+            "PT25H1M1S"
+        );
+    }
+
+    fn item_with_duration(duration: i64) -> Item {
+        Item {
+            duration,
+            ..Item::default()
+        }
+    }
+
+    #[test]
+    fn billing_rounds_up_to_the_increment() {
+        let billed = |duration| item_with_duration(duration).billed_duration(Some(15));
+        assert_eq!(billed(0), 0);
+        assert_eq!(billed(15 * MINUTE), 15 * MINUTE);
+        assert_eq!(billed(15 * MINUTE + 1), 30 * MINUTE);
+        assert_eq!(billed(MINUTE), 15 * MINUTE);
+    }
+
+    #[test]
+    fn billing_without_increment_keeps_the_time() {
+        let item = item_with_duration(7 * MINUTE + 1);
+        assert_eq!(item.billed_duration(None), 7 * MINUTE + 1);
+        assert_eq!(item.billed_duration(Some(0)), 7 * MINUTE + 1);
+    }
+
+    #[test]
+    fn costs_use_the_billed_time() {
+        let mut list = GroupList::new("Client".to_string());
+        list.hourly_rate = Some(100.0);
+        list.list.add(item_with_duration(50 * MINUTE));
+        list.list.add(item_with_duration(31 * MINUTE));
+        assert_eq!(list.cost(None), Some(135.0));
+        assert_eq!(list.cost(Some(15)), Some(175.0));
+        list.hourly_rate = None;
+        assert_eq!(list.cost(Some(15)), None);
+    }
+
+    #[test]
+    fn backspace_removes_whole_graphemes() {
+        let mut text = "cafe\u{301} 👍🏽".to_string();
+        pop_grapheme(&mut text);
+        assert_eq!(text, "cafe\u{301} ");
+        pop_grapheme(&mut text);
+        pop_grapheme(&mut text);
+        assert_eq!(text, "caf");
+        let mut family = "👨‍👩‍👧".to_string();
+        pop_grapheme(&mut family);
+        assert!(family.is_empty());
+        pop_grapheme(&mut family);
+        assert!(family.is_empty());
+    }
+
+    #[test]
+    fn dialog_backspace_removes_an_emoji_at_once() {
+        let mut app = app_with_items(&[]);
+        app.event(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        for c in "Tea 🍵".chars() {
+            app.event(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.event(KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(app.dialog.input.title, "Tea ");
+    }
+
+    fn round_trip(item: &Item) -> Item {
+        let saved = toml::to_string(item).expect("item saves");
+        toml::from_str(&saved).expect("item loads")
+    }
+
+    #[test]
+    fn item_with_subtasks_and_no_stops_saves() {
+        let item = Item {
+            title: "Write report".to_string(),
+            subtasks: vec![
+                Subtask {
+                    title: "Outline".to_string(),
+                    done: true,
+                },
+                Subtask {
+                    title: "Draft".to_string(),
+                    done: false,
+                },
+            ],
+            ..Item::default()
+        };
+        let loaded = round_trip(&item);
+        assert_eq!(loaded.title, "Write report");
+        assert_eq!(loaded.subtasks.len(), 2);
+        assert!(loaded.subtasks[0].done);
+        assert!(loaded.stops.is_empty());
+    }
+
+    #[test]
+    fn item_with_stops_saves() {
+        let item = Item {
+            duration: 90_000,
+            subtasks: vec![Subtask {
+                title: "Outline".to_string(),
+                done: false,
+            }],
+            stops: vec![Stop {
+                at: Local::now(),
+                duration: 60_000,
+                note: "got stuck".to_string(),
+            }],
+            ..Item::default()
+        };
+        let loaded = round_trip(&item);
+        assert_eq!(loaded.stops.len(), 1);
+        assert_eq!(loaded.stops[0].duration, 60_000);
+        assert_eq!(loaded.stops[0].note, "got stuck");
+        assert!(loaded.timers.is_empty());
+    }
+
+    #[test]
+    fn item_with_timers_saves() {
+        let item = Item {
+            duration: 150_000,
+            stops: vec![Stop {
+                at: Local::now(),
+                duration: 60_000,
+                note: String::new(),
+            }],
+            timers: vec![
+                SubTimer {
+                    name: "Review".to_string(),
+                    duration: 30_000,
+                    running: true,
+                },
+                SubTimer {
+                    name: "Calls".to_string(),
+                    duration: 0,
+                    running: false,
+                },
+            ],
+            ..Item::default()
+        };
+        let loaded = round_trip(&item);
+        assert_eq!(loaded.duration, 150_000);
+        assert_eq!(loaded.timers.len(), 2);
+        assert_eq!(loaded.timers[0].name, "Review");
+        assert_eq!(loaded.timers[0].duration, 30_000);
+        assert!(loaded.timers[0].running);
+        assert!(!loaded.timers[1].running);
+    }
+
+    #[test]
+    fn renaming_refuses_empty_and_taken_names() {
+        let mut app = app_with_items(&[]);
+        app.group_list.add(GroupList::new("Home".to_string()));
+        app.active_list = None;
+        app.group_list.state.select(Some(1));
+        app.event(KeyCode::Char('r'), KeyModifiers::NONE);
+        for _ in 0..4 {
+            app.event(KeyCode::Backspace, KeyModifiers::NONE);
+        }
+        app.event(KeyCode::Char(' '), KeyModifiers::NONE);
+        app.event(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.current_status(), Some("A list needs a name"));
+        assert!(app.rename.is_some());
+
+        for c in "Work ".chars() {
+            app.event(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        app.event(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.rename.is_some());
+        assert_eq!(app.group_list.items[1].name, "Home");
+
+        app.event(KeyCode::Backspace, KeyModifiers::NONE);
+        app.event(KeyCode::Char('s'), KeyModifiers::NONE);
+        app.event(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.rename.is_none());
+        assert_eq!(app.group_list.items[1].name, "Works");
+    }
+
+    #[test]
+    fn an_unreadable_wip_limit_keeps_the_old_one() {
+        let mut app = app_with_items(&[]);
+        app.group_list.items[0].wip_limit = Some(2);
+        for (text, limit) in [("abc", Some(2)), ("0", Some(2)), ("3", Some(3)), ("", None)].iter() {
+            app.dialog.edit_list(&app.group_list.items[0].clone());
+            app.dialog.points = text.to_string();
+            app.apply_list_settings();
+            assert_eq!(app.group_list.items[0].wip_limit, *limit, "{}", text);
+        }
+        assert_eq!(app.status.len(), 2);
+    }
+
+    #[test]
+    fn an_unreadable_timebox_keeps_the_old_one() {
+        let mut app = app_with_items(&[]);
+        app.group_list.items[0].timebox_minutes = Some(25);
+        for (text, minutes) in [
+            ("soon", Some(25)),
+            ("30s", Some(25)),
+            ("1h", Some(60)),
+            ("", None),
+        ]
+        .iter()
+        {
+            app.dialog.edit_list(&app.group_list.items[0].clone());
+            app.dialog.target = text.to_string();
+            app.apply_list_settings();
+            assert_eq!(
+                app.group_list.items[0].timebox_minutes, *minutes,
+                "{}",
+                text
+            );
+        }
+        assert_eq!(app.current_status(), Some("Invalid timebox \"30s\""));
     }
 }
@@ -0,0 +1,172 @@
+use crate::app::app::{format_duration, App, Item};
+use crate::app::due::start_of_week;
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone};
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Where `e` writes the week's time per item and day.
+const BREAKDOWN_FILE: &str = "breakdown.csv";
+
+/// Read-only totals of the board as they were at the end of `date`.
+pub struct AsOfView {
+    pub date: NaiveDate,
+}
+
+/// An item's time at `end`, and whether it is exact. Items finished by then
+/// count in full and stop notes record the time they had; otherwise the
+/// time is capped at what fits between the start and `end`.
+pub fn duration_at(item: &Item, end: DateTime<Local>) -> (i64, bool) {
+    match item.start_at {
+        Some(start_at) if start_at <= end => {}
+        _ => return (0, true),
+    }
+    if matches!(item.end_at, Some(end_at) if end_at <= end) {
+        return (item.duration, true);
+    }
+    if let Some(stop) = item.stops.iter().rev().find(|stop| stop.at <= end) {
+        return (stop.duration, true);
+    }
+    let span = item
+        .start_at
+        .map(|start_at| end.signed_duration_since(start_at).num_milliseconds())
+        .unwrap_or_default();
+    (item.duration.min(span), false)
+}
+
+impl App {
+    /// Opens the view at the end of last week.
+    pub fn open_as_of(&mut self) {
+        let today = Local::today().naive_local();
+        self.as_of = Some(AsOfView {
+            date: start_of_week(today, self.config.week_start) - Duration::days(1),
+        });
+    }
+
+    /// Left / Right step a day, Up / Down a week, e exports the week up to
+    /// the shown day by item and day, Esc closes.
+    pub fn process_as_of(&mut self, key: KeyCode) {
+        let view = match &mut self.as_of {
+            Some(view) => view,
+            None => return,
+        };
+        if let KeyCode::Char('e') = key {
+            let (from, to) = (view.date - Duration::days(6), view.date);
+            let message = match self.export_daily_breakdown_csv(BREAKDOWN_FILE, from, to) {
+                Ok(()) => format!("Time by day written to {}", BREAKDOWN_FILE),
+                Err(err) => format!("Could not write time by day: {}", err),
+            };
+            self.notify(message);
+            return;
+        }
+        let today = Local::today().naive_local();
+        let step = match key {
+            KeyCode::Left => Duration::days(-1),
+            KeyCode::Right => Duration::days(1),
+            KeyCode::Up => Duration::weeks(-1),
+            KeyCode::Down => Duration::weeks(1),
+            KeyCode::Esc => {
+                self.as_of = None;
+                return;
+            }
+            _ => return,
+        };
+        view.date = (view.date + step).min(today);
+    }
+
+    pub fn draw_as_of<B: Backend>(&self, frame: &mut Frame<B>) {
+        let view = match &self.as_of {
+            Some(view) => view,
+            None => return,
+        };
+        let size = frame.size();
+        let as_of_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let precision = self.config.duration_precision;
+        let end = Local
+            .from_local_datetime(&view.date.and_hms(23, 59, 59))
+            .single()
+            .unwrap_or_else(Local::now);
+        let logged = self
+            .daily_log
+            .range(..=view.date)
+            .map(|(_, millis)| millis)
+            .sum::<i64>();
+
+        let mut text = Text::from(vec![
+            Spans::from(vec![Span::styled(
+                format!("As of {}", view.date.format("%a %Y-%m-%d")),
+                Style::default()
+                    .fg(Color::LightCyan)
+                    .add_modifier(Modifier::BOLD),
+            )]),
+            Spans::from(vec![Span::raw(format!(
+                "Logged by then: {}",
+                format_duration(logged, precision)
+            ))]),
+        ]);
+        let mut estimated = false;
+        for list in &self.group_list.items {
+            let items = list
+                .list
+                .items
+                .iter()
+                .map(|item| (item, duration_at(item, end)))
+                .filter(|(_, (duration, _))| *duration > 0)
+                .collect::<Vec<_>>();
+            if items.is_empty() {
+                continue;
+            }
+            let total = items.iter().map(|(_, (duration, _))| duration).sum::<i64>();
+            text.lines.push(Spans::from(vec![Span::raw("")]));
+            text.lines.push(Spans::from(vec![Span::styled(
+                format!("{}  {}", list.name, format_duration(total, precision)),
+                Style::default().fg(Color::Yellow),
+            )]));
+            for (item, (duration, exact)) in items {
+                estimated |= !exact;
+                text.lines.push(Spans::from(vec![Span::raw(format!(
+                    "  {}{:>10}  {}",
+                    if exact { " " } else { "≤" },
+                    format_duration(duration, precision),
+                    item.title
+                ))]));
+            }
+        }
+        if estimated {
+            text.lines.push(Spans::from(vec![Span::raw("")]));
+            text.lines.push(Spans::from(vec![Span::styled(
+                "≤ items were running then, at most this much",
+                Style::default().fg(Color::Gray),
+            )]));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(
+                        " As of · Left/Right: day · Up/Down: week · e: export week · Esc: close ",
+                    )
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, as_of_size);
+        frame.render_widget(paragraph, as_of_size);
+    }
+}
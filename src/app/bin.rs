@@ -0,0 +1,284 @@
+use crate::app::app::{App, GroupList, Item};
+use crate::app::undo::Operation;
+
+use chrono::{DateTime, Duration, Local};
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+const BIN_LIMIT: usize = 50;
+
+/// A deleted list or item kept around so it can be restored later.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Deleted {
+    pub deleted_at: DateTime<Local>,
+    /// The deleted list's own position, or the list the item was in.
+    pub list_index: usize,
+    /// Position of a deleted item within its list.
+    #[serde(default)]
+    pub index: Option<usize>,
+    // TOML needs tables after plain values, so keep these last.
+    #[serde(default)]
+    pub item: Option<Item>,
+    #[serde(default)]
+    pub list: Option<GroupList<Item>>,
+}
+
+impl Deleted {
+    fn label(&self) -> String {
+        match (&self.item, &self.list) {
+            (Some(item), _) => format!("item  {}", item.title),
+            (_, Some(list)) => format!("list  {} ({} items)", list.name, list.list.items.len()),
+            _ => String::new(),
+        }
+    }
+}
+
+impl App {
+    pub fn bin_item(&mut self, list_index: usize, index: usize, item: Item) {
+        self.bin.push(Deleted {
+            deleted_at: Local::now(),
+            list_index,
+            index: Some(index),
+            item: Some(item),
+            list: None,
+        });
+        self.prune_bin();
+    }
+
+    pub fn bin_group(&mut self, index: usize, list: GroupList<Item>) {
+        self.bin.push(Deleted {
+            deleted_at: Local::now(),
+            list_index: index,
+            index: None,
+            item: None,
+            list: Some(list),
+        });
+        self.prune_bin();
+    }
+
+    /// Drops entries beyond the size limit or older than the configured retention.
+    pub fn prune_bin(&mut self) {
+        if self.config.bin_retention_days > 0 {
+            let oldest = Local::now() - Duration::days(self.config.bin_retention_days);
+            self.bin.retain(|deleted| deleted.deleted_at > oldest);
+        }
+        if self.bin.len() > BIN_LIMIT {
+            let excess = self.bin.len() - BIN_LIMIT;
+            self.bin.drain(..excess);
+        }
+    }
+
+    /// Forgets the bin entry of a deletion that was undone.
+    pub fn unbin_item(&mut self, id: u64) {
+        if let Some(pos) = self
+            .bin
+            .iter()
+            .rposition(|deleted| deleted.item.as_ref().map(|item| item.id) == Some(id))
+        {
+            self.bin.remove(pos);
+        }
+    }
+
+    pub fn unbin_group(&mut self, id: u64) {
+        if let Some(pos) = self
+            .bin
+            .iter()
+            .rposition(|deleted| deleted.list.as_ref().map(|list| list.id) == Some(id))
+        {
+            self.bin.remove(pos);
+        }
+    }
+
+    pub fn process_bin(&mut self, key: KeyCode) {
+        let row = self.bin_view.unwrap_or_default();
+        match key {
+            KeyCode::Esc | KeyCode::Char('b') => {
+                self.bin_view = None;
+            }
+            KeyCode::Up => {
+                self.bin_view = Some(row.saturating_sub(1));
+            }
+            KeyCode::Down if row + 1 < self.bin.len() => {
+                self.bin_view = Some(row + 1);
+            }
+            KeyCode::Enter => {
+                // Rows are shown newest first.
+                if row < self.bin.len() {
+                    let deleted = self.bin.remove(self.bin.len() - 1 - row);
+                    self.restore(deleted);
+                }
+                if self.bin.is_empty() {
+                    self.bin_view = None;
+                } else {
+                    self.bin_view = Some(row.min(self.bin.len() - 1));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Puts a deleted entry back. Its deletion can no longer be undone, the
+    /// restore can instead.
+    fn restore(&mut self, deleted: Deleted) {
+        if let Some(list) = deleted.list {
+            let index = deleted.list_index.min(self.group_list.items.len());
+            self.notify(format!("Restored list \"{}\"", list.name));
+            self.forget_deletion(|operation| {
+                matches!(operation, Operation::DeleteGroup { list: deleted, .. } if deleted.id == list.id)
+            });
+            let id = list.id;
+            self.group_list.items.insert(index, list);
+            if self.active_list.is_none() {
+                self.group_list.state.select(Some(index));
+            }
+            self.record(Operation::InsertGroup { id });
+        } else if let Some(mut item) = deleted.item {
+            let id = item.id;
+            self.forget_deletion(|operation| {
+                matches!(operation, Operation::DeleteItem { item, .. } if item.id == id)
+            });
+            if self.group_list.items.is_empty() {
+                let list = self.new_list("Restored".to_string());
+                self.group_list.add(list);
+            }
+            let list_index = if deleted.list_index < self.group_list.items.len() {
+                deleted.list_index
+            } else {
+                self.active_list.unwrap_or_default()
+            };
+            if self.get_item_by_id(item.id).is_some() {
+                item.id = self.new_id();
+            }
+            let id = item.id;
+            let list = &mut self.group_list.items[list_index];
+            let index = deleted.index.unwrap_or_default().min(list.list.items.len());
+            let message = format!("Restored \"{}\" to \"{}\"", item.title, list.name);
+            list.list.items.insert(index, item);
+            self.notify(message);
+            self.record(Operation::InsertItem { id });
+        }
+    }
+
+    fn get_item_by_id(&self, id: u64) -> Option<&Item> {
+        self.group_list
+            .items
+            .iter()
+            .flat_map(|list| list.list.items.iter())
+            .find(|item| item.id == id)
+    }
+
+    pub fn draw_bin<B: Backend>(&self, frame: &mut Frame<B>) {
+        let size = frame.size();
+        let bin_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let mut text = Text::default();
+        if self.bin.is_empty() {
+            text.lines
+                .push(Spans::from(vec![Span::raw("Nothing deleted")]));
+        }
+        for (row, deleted) in self.bin.iter().rev().enumerate() {
+            let style = if self.bin_view == Some(row) {
+                Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            text.lines.push(Spans::from(vec![
+                Span::styled(
+                    format!(
+                        "{}  ",
+                        deleted
+                            .deleted_at
+                            .format(&format!("%Y-%m-%d {}", self.config.clock_format.time()))
+                    ),
+                    Style::default().fg(Color::LightCyan),
+                ),
+                Span::styled(deleted.label(), style),
+            ]));
+        }
+
+        let bin = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Recently deleted · Enter: restore · Esc: close ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, bin_size);
+        frame.render_widget(bin, bin_size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::app::app_with_items;
+    use crossterm::event::KeyModifiers;
+
+    fn count(app: &App, title: &str) -> usize {
+        app.group_list
+            .items
+            .iter()
+            .flat_map(|list| list.list.items.iter())
+            .filter(|item| item.title == title)
+            .count()
+    }
+
+    #[test]
+    fn undo_after_restore_does_not_duplicate() {
+        let mut app = app_with_items(&["one"]);
+        app.group_list.items[0].list.state.select(Some(0));
+
+        app.event(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(count(&app, "one"), 0);
+        app.bin_view = Some(0);
+        app.process_bin(KeyCode::Enter);
+        assert_eq!(count(&app, "one"), 1);
+
+        // Undoing takes back the restore, not the deletion.
+        app.undo();
+        assert_eq!(count(&app, "one"), 0);
+        assert_eq!(app.bin.len(), 1);
+        app.undo();
+        assert_eq!(count(&app, "one"), 0);
+        app.redo();
+        assert_eq!(count(&app, "one"), 1);
+    }
+
+    #[test]
+    fn restoring_a_list_can_be_undone() {
+        let mut app = App::new("Test".to_string());
+        let work = app.new_list("Work".to_string());
+        app.group_list.add(work);
+        let home = app.new_list("Home".to_string());
+        app.group_list.add(home);
+        app.group_list.state.select(Some(1));
+
+        app.event(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(app.group_list.items.len(), 1);
+        app.bin_view = Some(0);
+        app.process_bin(KeyCode::Enter);
+        assert_eq!(app.group_list.items.len(), 2);
+
+        app.undo();
+        assert_eq!(app.group_list.items.len(), 1);
+        app.undo();
+        assert_eq!(app.group_list.items.len(), 1);
+    }
+}
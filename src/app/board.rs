@@ -0,0 +1,131 @@
+use crate::app::app::{App, Filter, GroupList, Item};
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+const COLUMNS: [&str; 3] = ["To-Do", "In Progress", "Done"];
+
+/// Indices of the visible tasks, split into to-do, in progress and done.
+fn board_columns(items: &[Item], filter: Filter) -> [Vec<usize>; 3] {
+    let mut columns = [Vec::new(), Vec::new(), Vec::new()];
+    for (index, item) in items.iter().enumerate() {
+        if !item.is_task() || !filter.matches(item) {
+            continue;
+        }
+        let column = if item.done() {
+            2
+        } else if item.started() {
+            1
+        } else {
+            0
+        };
+        columns[column].push(index);
+    }
+    columns
+}
+
+/// Column and row of the item at `index`.
+fn position(columns: &[Vec<usize>; 3], index: usize) -> Option<(usize, usize)> {
+    columns.iter().enumerate().find_map(|(column, rows)| {
+        rows.iter()
+            .position(|row| *row == index)
+            .map(|row| (column, row))
+    })
+}
+
+impl App {
+    /// Handles the arrow keys while the board is shown. Returns false for keys
+    /// the regular list navigation should handle, like Left on the first column.
+    pub fn board_navigate(&mut self, key: KeyCode, modi: KeyModifiers) -> bool {
+        let list_index = match self.active_list {
+            Some(list_index) if self.board && modi == KeyModifiers::NONE => list_index,
+            _ => return false,
+        };
+        let list = &mut self.group_list.items[list_index].list;
+        let columns = board_columns(&list.items, self.filter);
+        let current = list
+            .state
+            .selected()
+            .and_then(|index| position(&columns, index));
+
+        let (column, row) = match (key, current) {
+            (KeyCode::Up, Some((column, row))) => (column, row.saturating_sub(1)),
+            (KeyCode::Down, Some((column, row))) => (column, row + 1),
+            (KeyCode::Left, Some((0, _))) => return false,
+            (KeyCode::Left, Some((column, row))) => (column - 1, row),
+            (KeyCode::Right, Some((column, row))) => ((column + 1).min(2), row),
+            (KeyCode::Up, None) | (KeyCode::Down, None) | (KeyCode::Right, None) => {
+                match columns.iter().position(|rows| !rows.is_empty()) {
+                    Some(column) => (column, 0),
+                    None => return true,
+                }
+            }
+            _ => return false,
+        };
+
+        let rows = &columns[column];
+        if !rows.is_empty() {
+            list.state.select(Some(rows[row.min(rows.len() - 1)]));
+        }
+        true
+    }
+
+    pub fn draw_board<B: Backend>(
+        &self,
+        frame: &mut Frame<B>,
+        group_list: &GroupList<Item>,
+        area: Rect,
+    ) {
+        let columns = board_columns(&group_list.list.items, self.filter);
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+            ])
+            .split(area);
+
+        let style = if self.active_list.is_some() {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+
+        for (column, rows) in columns.iter().enumerate() {
+            let list = List::new(
+                rows.iter()
+                    .map(|index| {
+                        ListItem::new(Span::raw(group_list.list.items[*index].title.clone()))
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .block(
+                Block::default()
+                    .title(format!(" {} ({}) ", COLUMNS[column], rows.len()))
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Black)),
+            )
+            .style(style)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+            .highlight_symbol("> ");
+
+            let mut state = ListState::default();
+            state.select(
+                group_list
+                    .list
+                    .state
+                    .selected()
+                    .and_then(|selected| rows.iter().position(|index| *index == selected)),
+            );
+            frame.render_stateful_widget(list, layout[column], &mut state);
+        }
+    }
+}
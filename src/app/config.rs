@@ -0,0 +1,268 @@
+use chrono::{DateTime, Datelike, Local, Weekday};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub enum TomlFormat {
+    #[default]
+    Compact,
+    Pretty,
+}
+
+/// Smallest unit shown when formatting durations.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum DurationPrecision {
+    #[default]
+    Seconds,
+    Minutes,
+    Hours,
+}
+
+impl DurationPrecision {
+    pub fn millis(self) -> i64 {
+        match self {
+            DurationPrecision::Seconds => 1_000,
+            DurationPrecision::Minutes => 60_000,
+            DurationPrecision::Hours => 3_600_000,
+        }
+    }
+}
+
+/// How times of day are shown.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ClockFormat {
+    #[default]
+    H24,
+    H12,
+}
+
+impl ClockFormat {
+    pub fn next(self) -> Self {
+        match self {
+            ClockFormat::H24 => ClockFormat::H12,
+            ClockFormat::H12 => ClockFormat::H24,
+        }
+    }
+
+    /// Format string for a time of day.
+    pub fn time(self) -> &'static str {
+        match self {
+            ClockFormat::H24 => "%H:%M",
+            ClockFormat::H12 => "%I:%M %p",
+        }
+    }
+
+    /// Format string for a full timestamp, like RFC 2822 for `H24`.
+    pub fn date_time(self) -> &'static str {
+        match self {
+            ClockFormat::H24 => "%a, %d %b %Y %H:%M:%S %z",
+            ClockFormat::H12 => "%a, %d %b %Y %I:%M:%S %p %z",
+        }
+    }
+}
+
+/// What Left does while no list is open.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum LeftAction {
+    #[default]
+    Nothing,
+    /// Returns to where the last jump or list switch came from.
+    Back,
+    /// Hides the detail pane until a list is opened again.
+    CollapseDetail,
+}
+
+/// A named database file that can be switched to from within the app.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Workspace {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Config {
+    pub autostart_on_create: bool,
+    /// Starting or resuming a timer pauses all other running ones.
+    pub single_timer: bool,
+    pub toml_format: TomlFormat,
+    pub duration_precision: DurationPrecision,
+    pub clock_format: ClockFormat,
+    /// Timestamps start with the weekday and ISO week, like "Mon W23 14:02".
+    pub show_week_numbers: bool,
+    /// Deleting a list with more items than this asks for confirmation.
+    pub confirm_delete_threshold: usize,
+    /// Running items past these many minutes are highlighted, 0 disables.
+    pub long_running_warning_minutes: i64,
+    pub long_running_alert_minutes: i64,
+    /// Pauses all running timers when quitting with Ctrl+q.
+    pub stop_timers_on_quit: bool,
+    /// Ctrl+q first shows what is saved and quits on `y`, Esc stays.
+    pub confirm_quit: bool,
+    /// Ask before Alt+s wipes the times of a done item.
+    pub confirm_restart_done: bool,
+    /// Asks for a short note whenever an item is marked done.
+    pub note_on_stop: bool,
+    /// Up/Down jump from the last entry back to the first and vice versa.
+    pub wrap_navigation: bool,
+    /// Up/Down step over done items or dividers. With nothing left to land
+    /// on, the selection stays where it is.
+    pub skip_done_in_nav: bool,
+    pub skip_dividers: bool,
+    /// Enter on a selected list opens it, like Right.
+    pub enter_opens_list: bool,
+    /// Inside a list, Enter starts, pauses and resumes the selected item
+    /// instead of doing nothing. Dialogs still take Enter to submit.
+    pub enter_toggles_timer: bool,
+    /// A newly created list is opened right away, ready for its first item.
+    pub enter_new_list: bool,
+    /// Ctrl+Up/Down past the first or last item moves it into the list
+    /// above or below instead of wrapping around.
+    pub move_across_lists: bool,
+    /// Shows the lists on the right and the items on the left.
+    pub layout_reversed: bool,
+    /// Items are done once all their subtasks are, and open again when a
+    /// subtask is reopened or added.
+    pub auto_done_on_subtasks: bool,
+    /// The lists take the whole width while no item is selected or shown.
+    pub collapse_empty_detail: bool,
+    pub left_at_top: LeftAction,
+    /// Rows of open items show how long they were not worked on.
+    pub show_idle_in_rows: bool,
+    /// Lists show a bar of their done items after the name.
+    pub show_list_progress: bool,
+    /// Done items stay visible but dimmed and crossed out.
+    pub dim_done: bool,
+    /// Items marked done move to the end of their list and back when they
+    /// are reopened. In manual order this becomes their place; the other
+    /// sort modes and pinning still decide the order when the list is sorted.
+    pub auto_sink_done: bool,
+    /// Long item titles are cut to the row width with an ellipsis.
+    pub truncate_titles: bool,
+    /// Lists without activity for this many days are dimmed, 0 disables.
+    pub stale_after_days: i64,
+    /// Items blocked for this many days are shown in red, 0 disables.
+    pub blocked_warning_days: i64,
+    /// Deleted entries older than this many days leave the bin, 0 keeps them.
+    pub bin_retention_days: i64,
+    /// Paused items keep logging time, for tracking how long a task was
+    /// open rather than worked on. They still show as paused.
+    pub count_paused: bool,
+    /// The last changes are saved with the database and can still be
+    /// undone after a restart.
+    pub persist_undo: bool,
+    /// Running items pulse after this many minutes without a key press, 0 disables.
+    pub idle_minutes: i64,
+    /// Also ring the terminal bell once when going idle.
+    pub idle_bell: bool,
+    /// Every this many minutes of a running timer, ring the bell and say how
+    /// long it has been running.
+    pub reminder_interval: Option<i64>,
+    /// Shell command run when an item is marked done. `{title}`, `{duration}`
+    /// and `{list}` are replaced with quoted values.
+    pub on_done_command: Option<String>,
+    /// List that quick capture adds to, "Inbox" if unset.
+    pub inbox: Option<String>,
+    /// Each item's time is rounded up to this many minutes for billing.
+    pub billing_increment: Option<i64>,
+    /// Minutes to track per day, shown as a gauge in the status bar.
+    pub daily_goal: Option<i64>,
+    /// Time per tag splits an item's time evenly between its tags instead of
+    /// counting it in full for each.
+    pub split_tag_time: bool,
+    /// First day of the week, like "Mon" or "Sun", for week boundaries such
+    /// as the "end of week" due date.
+    pub week_start: Weekday,
+    /// Text file read by the import, one item title per line.
+    pub import_file: String,
+    /// CSV file read by Ctrl+o, one item per row.
+    pub csv_file: String,
+    /// File a single item is shared through with `s` and Alt+i.
+    pub snippet_file: String,
+    /// Text file the screen is written to with Alt+z.
+    pub snapshot_file: String,
+    /// Smallest size of the new / edit dialog, unless the terminal is smaller.
+    pub dialog_min_width: u16,
+    pub dialog_min_height: u16,
+    /// Hints shown while there are no lists, or no items in a list.
+    pub empty_lists_message: String,
+    pub empty_list_message: String,
+    // TOML needs tables after plain values, so keep this last.
+    pub workspaces: Vec<Workspace>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            autostart_on_create: false,
+            single_timer: false,
+            toml_format: TomlFormat::default(),
+            duration_precision: DurationPrecision::default(),
+            clock_format: ClockFormat::default(),
+            show_week_numbers: false,
+            confirm_delete_threshold: 0,
+            long_running_warning_minutes: 120,
+            long_running_alert_minutes: 240,
+            stop_timers_on_quit: false,
+            confirm_quit: true,
+            confirm_restart_done: true,
+            note_on_stop: false,
+            wrap_navigation: true,
+            skip_done_in_nav: false,
+            skip_dividers: false,
+            enter_opens_list: true,
+            enter_toggles_timer: false,
+            enter_new_list: false,
+            move_across_lists: false,
+            layout_reversed: false,
+            auto_done_on_subtasks: false,
+            collapse_empty_detail: false,
+            left_at_top: LeftAction::Nothing,
+            show_idle_in_rows: false,
+            show_list_progress: false,
+            dim_done: false,
+            auto_sink_done: false,
+            truncate_titles: false,
+            stale_after_days: 14,
+            blocked_warning_days: 3,
+            bin_retention_days: 30,
+            count_paused: false,
+            persist_undo: false,
+            idle_minutes: 30,
+            idle_bell: false,
+            reminder_interval: None,
+            on_done_command: None,
+            inbox: None,
+            billing_increment: None,
+            daily_goal: None,
+            split_tag_time: false,
+            week_start: Weekday::Mon,
+            import_file: "import.txt".to_string(),
+            csv_file: "import.csv".to_string(),
+            snippet_file: "snippet.toml".to_string(),
+            snapshot_file: "snapshot.txt".to_string(),
+            dialog_min_width: 40,
+            dialog_min_height: 8,
+            empty_lists_message: "No lists yet — press Ctrl+N to create one".to_string(),
+            empty_list_message: "No items yet — open the list and press Ctrl+N to add one"
+                .to_string(),
+            workspaces: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Formats `at`, led by the weekday and ISO week with `show_week_numbers`,
+    /// which then replace a weekday at the start of `format`.
+    pub fn timestamp(&self, at: DateTime<Local>, format: &str) -> String {
+        if self.show_week_numbers {
+            format!(
+                "{} W{:02} {}",
+                at.weekday(),
+                at.iso_week().week(),
+                at.format(format.trim_start_matches("%a, ").trim_start_matches("%a "))
+            )
+        } else {
+            at.format(format).to_string()
+        }
+    }
+}
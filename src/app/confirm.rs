@@ -0,0 +1,200 @@
+use crate::app::app::App;
+use crate::app::undo::Operation;
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::Span,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// An action waiting for the user to confirm it with `y`.
+pub enum Confirm {
+    DeleteGroup(usize),
+    ResetTimer {
+        list_index: usize,
+        id: u64,
+    },
+    MergeGroups {
+        source: usize,
+        target: usize,
+    },
+    /// Starting the item would exceed its list's WIP limit.
+    StartOverWip {
+        list_index: usize,
+        id: u64,
+    },
+    /// Saving and quitting, which the main loop does once confirmed.
+    Quit,
+}
+
+impl App {
+    fn confirm_message(&self, confirm: &Confirm) -> String {
+        match confirm {
+            Confirm::DeleteGroup(index) => match self.group_list.items.get(*index) {
+                Some(list) => format!(
+                    "Delete list \"{}\" with {} items?",
+                    list.name,
+                    list.list.items.len()
+                ),
+                None => "Delete list?".to_string(),
+            },
+            Confirm::ResetTimer { list_index, id } => match self.find_item(*list_index, *id) {
+                Some(item) => format!("Reset the timer of \"{}\"?", item.title),
+                None => "Reset timer?".to_string(),
+            },
+            Confirm::MergeGroups { source, target } => match (
+                self.group_list.items.get(*source),
+                self.group_list.items.get(*target),
+            ) {
+                (Some(source), Some(target)) => format!(
+                    "Move {} items from \"{}\" into \"{}\" and remove \"{}\"?",
+                    source.list.items.len(),
+                    source.name,
+                    target.name,
+                    source.name
+                ),
+                _ => "Merge lists?".to_string(),
+            },
+            Confirm::StartOverWip { list_index, id } => {
+                match (
+                    self.group_list.items.get(*list_index),
+                    self.find_item(*list_index, *id),
+                ) {
+                    (Some(list), Some(item)) => format!(
+                        "\"{}\" already has {} of {} items in progress. Start \"{}\" anyway?",
+                        list.name,
+                        list.in_progress(),
+                        list.wip_limit.unwrap_or_default(),
+                        item.title
+                    ),
+                    _ => "Exceed the WIP limit?".to_string(),
+                }
+            }
+            Confirm::Quit => format!(
+                "Save {} lists, {} items to {} and quit?",
+                self.group_list.items.len(),
+                self.group_list
+                    .items
+                    .iter()
+                    .map(|list| list.list.items.len())
+                    .sum::<usize>(),
+                self.path
+            ),
+        }
+    }
+
+    pub fn quit_pending(&self) -> bool {
+        matches!(self.confirm, Some(Confirm::Quit))
+    }
+
+    pub fn process_confirm(&mut self, confirmed: bool) {
+        if let Some(confirm) = self.confirm.take() {
+            if confirmed {
+                match confirm {
+                    Confirm::DeleteGroup(index) => self.delete_group(index),
+                    Confirm::ResetTimer { list_index, id } => self.reset_timer(list_index, id),
+                    Confirm::MergeGroups { source, target } => self.merge_groups(source, target),
+                    Confirm::StartOverWip { list_index, id } => {
+                        if let Some(item) = self.find_item_mut(list_index, id) {
+                            let previous = item.clone();
+                            item.start();
+                            self.record(Operation::Edit {
+                                list_index,
+                                item: previous,
+                            });
+                        }
+                    }
+                    Confirm::Quit => {}
+                }
+            }
+        }
+    }
+
+    pub fn delete_group(&mut self, index: usize) {
+        if index < self.group_list.items.len() {
+            let list = self.group_list.items.remove(index);
+            self.group_list.state.select(None);
+            self.bin_group(index, list.clone());
+            self.record(Operation::DeleteGroup { index, list });
+        }
+    }
+
+    /// Moves all items of `source` to the end of `target` and removes the
+    /// emptied `source` list.
+    pub fn merge_groups(&mut self, source: usize, target: usize) {
+        if let Some(operation) = self.merge_lists(source, target) {
+            if let Operation::Merge { list, count, .. } = &operation {
+                let target = self.group_list.state.selected().unwrap_or_default();
+                self.notify(format!(
+                    "Merged {} items from \"{}\" into \"{}\"",
+                    count, list.name, self.group_list.items[target].name
+                ));
+            }
+            self.record(operation);
+        }
+    }
+
+    /// Does the merge of `merge_groups` and returns the operation undoing it.
+    pub fn merge_lists(&mut self, source: usize, target: usize) -> Option<Operation> {
+        let len = self.group_list.items.len();
+        if source == target || source >= len || target >= len {
+            return None;
+        }
+        let items = std::mem::take(&mut self.group_list.items[source].list.items);
+        let count = items.len();
+        self.group_list.items[target].list.items.extend(items);
+        let list = self.group_list.items.remove(source);
+
+        self.active_list = None;
+        self.group_list
+            .state
+            .select(Some(if target > source { target - 1 } else { target }));
+        Some(Operation::Merge {
+            source,
+            target,
+            list,
+            count,
+        })
+    }
+
+    pub fn reset_timer(&mut self, list_index: usize, id: u64) {
+        if let Some(item) = self.find_item_mut(list_index, id) {
+            let previous = item.clone();
+            item.reset();
+            self.record(Operation::Edit {
+                list_index,
+                item: previous,
+            });
+        }
+    }
+
+    pub fn draw_confirm<B: Backend>(&self, frame: &mut Frame<B>) {
+        if let Some(confirm) = &self.confirm {
+            let size = frame.size();
+            let confirm_size = Rect::new(
+                size.x + size.width / 4,
+                size.y + size.height / 2 - size.height.min(4) / 2,
+                size.width / 2,
+                size.height.min(4),
+            );
+
+            let message = format!("{} (y/n)", self.confirm_message(confirm));
+            let paragraph = Paragraph::new(Span::raw(message))
+                .block(
+                    Block::default()
+                        .title(" Confirm ")
+                        .borders(Borders::ALL)
+                        .style(Style::default().bg(Color::Red)),
+                )
+                .style(Style::default().fg(Color::White).bg(Color::Red))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+
+            frame.render_widget(Clear, confirm_size);
+            frame.render_widget(paragraph, confirm_size);
+        }
+    }
+}
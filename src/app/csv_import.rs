@@ -0,0 +1,251 @@
+use crate::app::app::{App, Item, DUE_FORMAT};
+
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone};
+use crossterm::event::KeyCode;
+use std::error::Error;
+use std::fs;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Columns read when the file has no header row.
+const COLUMNS: [&str; 5] = ["list", "title", "started", "ended", "duration_seconds"];
+
+/// Items read from the CSV file with the list each goes into, waiting to be
+/// added to or to replace the current lists.
+pub struct CsvPreview {
+    pub rows: Vec<(String, Item)>,
+    /// Rows that could not be read.
+    pub skipped: usize,
+}
+
+/// Splits a CSV line into fields, with quotes around fields holding commas
+/// and doubled quotes inside them.
+fn split_row(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Reads RFC 3339 or `YYYY-MM-DD HH:MM`, an empty field is `None`.
+fn parse_time(field: &str) -> Result<Option<DateTime<Local>>, ()> {
+    if field.is_empty() {
+        return Ok(None);
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(field) {
+        return Ok(Some(time.with_timezone(&Local)));
+    }
+    NaiveDateTime::parse_from_str(field, DUE_FORMAT)
+        .ok()
+        .and_then(|naive| Local.from_local_datetime(&naive).single())
+        .map(Some)
+        .ok_or(())
+}
+
+/// The item of one row, `None` if a field can't be read or the list or
+/// title is missing.
+fn read_row(columns: &[String], fields: &[String]) -> Option<(String, Item)> {
+    let field = |name: &str| {
+        columns
+            .iter()
+            .position(|column| column == name)
+            .and_then(|index| fields.get(index))
+            .map(|field| field.trim())
+            .unwrap_or_default()
+    };
+    let (list, title) = (field("list"), field("title"));
+    if list.is_empty() || title.is_empty() {
+        return None;
+    }
+    let duration = match field("duration_seconds") {
+        "" => 0,
+        seconds => Duration::seconds(seconds.parse().ok()?).num_milliseconds(),
+    };
+    let tags = field("tags")
+        .split(';')
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    let (start_at, end_at) = (
+        parse_time(field("started")).ok()?,
+        parse_time(field("ended")).ok()?,
+    );
+    // Started but unfinished rows come in paused rather than running.
+    let item = Item {
+        title: title.to_string(),
+        desc: field("desc").to_string(),
+        start_at,
+        end_at,
+        paused: start_at.is_some() && end_at.is_none(),
+        duration,
+        tags,
+        ..Item::default()
+    };
+    Some((list.to_string(), item))
+}
+
+impl App {
+    /// Reads rows of list, title, started, ended and duration in seconds,
+    /// named by a header row if the file has one, which may add desc and
+    /// tags separated by ";". Unreadable rows are counted and skipped.
+    pub fn import_csv(&self, path: &str) -> Result<CsvPreview, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let mut columns = COLUMNS
+            .iter()
+            .map(|column| column.to_string())
+            .collect::<Vec<_>>();
+        let mut first = lines.next().map(split_row);
+        if let Some(header) = &first {
+            if header
+                .iter()
+                .any(|field| field.trim().eq_ignore_ascii_case("title"))
+            {
+                columns = header
+                    .iter()
+                    .map(|field| field.trim().to_lowercase())
+                    .collect();
+                first = None;
+            }
+        }
+
+        let mut preview = CsvPreview {
+            rows: Vec::new(),
+            skipped: 0,
+        };
+        for fields in first.into_iter().chain(lines.map(split_row)) {
+            match read_row(&columns, &fields) {
+                Some(row) => preview.rows.push(row),
+                None => preview.skipped += 1,
+            }
+        }
+        Ok(preview)
+    }
+
+    pub fn start_csv_import(&mut self) {
+        let path = self.config.csv_file.clone();
+        match self.import_csv(&path) {
+            Ok(preview) if preview.rows.is_empty() => {
+                self.notify(format!(
+                    "Nothing to import in {}, {} rows skipped",
+                    path, preview.skipped
+                ));
+            }
+            Ok(preview) => self.csv_import = Some(preview),
+            Err(err) => self.notify(format!("Could not read {}: {}", path, err)),
+        }
+    }
+
+    /// Enter adds the rows to the lists, r first moves all lists to the
+    /// bin, Esc cancels.
+    pub fn process_csv_import(&mut self, key: KeyCode) {
+        let replace = match key {
+            KeyCode::Enter => false,
+            KeyCode::Char('r') => true,
+            KeyCode::Esc => {
+                self.csv_import = None;
+                return;
+            }
+            _ => return,
+        };
+        let preview = match self.csv_import.take() {
+            Some(preview) => preview,
+            None => return,
+        };
+        if replace {
+            while let Some(list) = self.group_list.items.pop() {
+                let index = self.group_list.items.len();
+                self.bin_group(index, list);
+            }
+            self.active_list = None;
+            self.clamp_selection();
+        }
+
+        let count = preview.rows.len();
+        for (name, mut item) in preview.rows {
+            item.id = self.new_id();
+            let index = match self
+                .group_list
+                .items
+                .iter()
+                .position(|list| list.name == name)
+            {
+                Some(index) => index,
+                None => {
+                    let list = self.new_list(name);
+                    self.group_list.items.push(list);
+                    self.group_list.items.len() - 1
+                }
+            };
+            self.group_list.items[index].list.add(item);
+        }
+        self.sort_lists();
+        self.notify(match preview.skipped {
+            0 => format!("Imported {} items", count),
+            skipped => format!("Imported {} items, skipped {} rows", count, skipped),
+        });
+    }
+
+    pub fn draw_csv_import<B: Backend>(&self, frame: &mut Frame<B>) {
+        let preview = match &self.csv_import {
+            Some(preview) => preview,
+            None => return,
+        };
+        let size = frame.size();
+        let preview_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let mut text = Text::from(Spans::from(vec![Span::styled(
+            format!(
+                "{} items from {}, {} rows skipped",
+                preview.rows.len(),
+                self.config.csv_file,
+                preview.skipped
+            ),
+            Style::default().fg(Color::LightCyan),
+        )]));
+        for (list, item) in &preview.rows {
+            text.lines.push(Spans::from(vec![
+                Span::styled(format!("  {}  ", list), Style::default().fg(Color::Yellow)),
+                Span::raw(item.title.clone()),
+            ]));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" CSV import · Enter: add · r: replace all lists · Esc: cancel ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, preview_size);
+        frame.render_widget(paragraph, preview_size);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::app::app::{App, DUE_FORMAT};
+use crate::app::undo::Operation;
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Weekday};
+
+/// Resolves phrases like "tomorrow", "friday", "next monday", "end of week"
+/// or "in 2 weeks" against `now`. Days are due at their end, like bare dates.
+pub fn parse_relative(
+    input: &str,
+    now: DateTime<Local>,
+    week_start: Weekday,
+) -> Option<DateTime<Local>> {
+    let input = input.trim().to_lowercase();
+    let words = input.split_whitespace().collect::<Vec<_>>();
+    let days = match words.as_slice() {
+        ["today"] => 0,
+        ["tomorrow"] => 1,
+        ["next", "week"] => 7,
+        ["end", "of", "week"] | ["eow"] => {
+            let today = now.date().naive_local();
+            (start_of_week(today, week_start) - today).num_days() + 6
+        }
+        ["in", amount, unit] => {
+            let amount = amount.parse::<i64>().ok()?;
+            return match unit.trim_end_matches('s') {
+                "min" | "minute" => Some(now + Duration::minutes(amount)),
+                "hour" => Some(now + Duration::hours(amount)),
+                "day" => end_of_day(now + Duration::days(amount)),
+                "week" => end_of_day(now + Duration::weeks(amount)),
+                _ => None,
+            };
+        }
+        ["next", day] | [day] => days_until(day.parse::<Weekday>().ok()?, now.weekday()),
+        _ => return None,
+    };
+    end_of_day(now + Duration::days(days))
+}
+
+/// The first day of the week containing `date`.
+pub fn start_of_week(date: NaiveDate, week_start: Weekday) -> NaiveDate {
+    let offset = (7 + date.weekday().num_days_from_monday() as i64
+        - week_start.num_days_from_monday() as i64)
+        % 7;
+    date - Duration::days(offset)
+}
+
+/// Days until the next `day`, a full week if that is today.
+fn days_until(day: Weekday, today: Weekday) -> i64 {
+    let days = (7 + day.num_days_from_monday() as i64 - today.num_days_from_monday() as i64) % 7;
+    if days == 0 {
+        7
+    } else {
+        days
+    }
+}
+
+fn end_of_day(time: DateTime<Local>) -> Option<DateTime<Local>> {
+    time.date().and_hms_opt(23, 59, 59)
+}
+
+impl App {
+    /// Moves the due date of the selected item by `days`. Moving it back
+    /// stops at today, so `-` never makes an item overdue.
+    pub fn bump_due(&mut self, days: i64) {
+        let list_index = match self.active_list {
+            Some(index) => index,
+            None => return,
+        };
+        let now = Local::now();
+        let message = match self.get_selected_unlocked() {
+            Some(item) => match item.due_at {
+                Some(due_at) => {
+                    let previous = item.clone();
+                    let bumped = due_at + Duration::days(days);
+                    if days < 0 && bumped.date() < now.date() {
+                        Some("Due date can't be moved into the past".to_string())
+                    } else {
+                        item.due_at = Some(bumped);
+                        let message = format!("Due {}", bumped.format(DUE_FORMAT));
+                        self.record(Operation::Edit {
+                            list_index,
+                            item: previous,
+                        });
+                        self.sort_active_list();
+                        Some(message)
+                    }
+                }
+                None => None,
+            },
+            None => None,
+        };
+        if let Some(message) = message {
+            self.notify(message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(day: u32) -> NaiveDate {
+        // 2024-06-03 is a Monday.
+        NaiveDate::from_ymd(2024, 6, day)
+    }
+
+    #[test]
+    fn weeks_start_on_the_configured_day() {
+        assert_eq!(start_of_week(date(3), Weekday::Mon), date(3));
+        assert_eq!(start_of_week(date(9), Weekday::Mon), date(3));
+        assert_eq!(start_of_week(date(10), Weekday::Mon), date(10));
+        assert_eq!(start_of_week(date(8), Weekday::Sun), date(2));
+        assert_eq!(start_of_week(date(9), Weekday::Sun), date(9));
+        assert_eq!(start_of_week(date(3), Weekday::Sun), date(2));
+    }
+
+    #[test]
+    fn end_of_week_is_the_day_before_the_next_start() {
+        let end_of_week = |day, week_start| {
+            let now = Local
+                .from_local_datetime(&date(day).and_hms(12, 0, 0))
+                .unwrap();
+            parse_relative("end of week", now, week_start).map(|at| at.date().naive_local())
+        };
+        assert_eq!(end_of_week(3, Weekday::Mon), Some(date(9)));
+        assert_eq!(end_of_week(9, Weekday::Mon), Some(date(9)));
+        assert_eq!(end_of_week(9, Weekday::Sun), Some(date(15)));
+        assert_eq!(end_of_week(8, Weekday::Sun), Some(date(8)));
+    }
+
+    #[test]
+    fn weekdays_are_always_ahead() {
+        let monday = Local
+            .from_local_datetime(&date(3).and_hms(12, 0, 0))
+            .unwrap();
+        let due =
+            |input| parse_relative(input, monday, Weekday::Mon).map(|at| at.date().naive_local());
+        assert_eq!(due("friday"), Some(date(7)));
+        assert_eq!(due("monday"), Some(date(10)));
+        assert_eq!(due("next monday"), Some(date(10)));
+        assert_eq!(due("tomorrow"), Some(date(4)));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::app::app::{App, GroupList, Item};
+
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+
+/// What the screen showed when it was frozen. Timers keep running underneath.
+pub struct Frozen {
+    pub at: DateTime<Local>,
+    /// Duration and time tracked today per item id.
+    durations: HashMap<u64, (i64, i64)>,
+}
+
+impl App {
+    pub fn toggle_freeze(&mut self) {
+        self.frozen = match self.frozen {
+            Some(_) => None,
+            None => Some(Frozen {
+                at: Local::now(),
+                durations: self
+                    .group_list
+                    .items
+                    .iter()
+                    .flat_map(|list| list.list.items.iter())
+                    .map(|item| (item.id, (item.duration, item.tracked_today)))
+                    .collect(),
+            }),
+        };
+    }
+
+    /// The item with its durations as displayed.
+    pub fn shown(&self, item: &Item) -> Item {
+        let mut item = item.clone();
+        if let Some(frozen) = &self.frozen {
+            if let Some((duration, tracked_today)) = frozen.durations.get(&item.id) {
+                item.duration = *duration;
+                item.tracked_today = *tracked_today;
+            }
+        }
+        item
+    }
+
+    pub fn shown_now(&self) -> DateTime<Local> {
+        match &self.frozen {
+            Some(frozen) => frozen.at,
+            None => Local::now(),
+        }
+    }
+
+    pub fn shown_total_duration(&self) -> i64 {
+        self.group_list
+            .items
+            .iter()
+            .flat_map(|list| list.list.items.iter())
+            .map(|item| self.shown(item).duration)
+            .sum()
+    }
+
+    pub fn shown_today_duration(&self, list: &GroupList<Item>) -> i64 {
+        list.list
+            .items
+            .iter()
+            .map(|item| self.shown(item).duration_today())
+            .sum()
+    }
+}
@@ -0,0 +1,120 @@
+use crate::app::app::{App, Item};
+
+/// Field the open list is grouped by, drawn as header rows between items.
+/// The list itself keeps its order, only the display is regrouped.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum GroupBy {
+    #[default]
+    None,
+    Tag,
+    Status,
+}
+
+/// A line of the grouped list: either a header or the index of an item.
+pub enum Row {
+    Header(String),
+    Item(usize),
+}
+
+const NO_TAG: &str = "No tag";
+const STATUSES: [&str; 4] = ["Running", "Paused", "Not started", "Done"];
+
+impl GroupBy {
+    pub fn next(self) -> GroupBy {
+        match self {
+            GroupBy::None => GroupBy::Tag,
+            GroupBy::Tag => GroupBy::Status,
+            GroupBy::Status => GroupBy::None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GroupBy::None => "",
+            GroupBy::Tag => "[grouped by tag] ",
+            GroupBy::Status => "[grouped by status] ",
+        }
+    }
+
+    /// The header an item is shown under. Items with several tags are
+    /// grouped by their first one.
+    fn header(self, item: &Item) -> String {
+        match self {
+            GroupBy::None => String::new(),
+            GroupBy::Tag => item
+                .tags
+                .first()
+                .cloned()
+                .unwrap_or_else(|| NO_TAG.to_string()),
+            GroupBy::Status => {
+                let status = if item.done() {
+                    3
+                } else if item.running() {
+                    0
+                } else if item.started() {
+                    1
+                } else {
+                    2
+                };
+                STATUSES[status].to_string()
+            }
+        }
+    }
+
+    /// Where a header goes: tags sort by name with untagged items last,
+    /// statuses in the order work moves through them.
+    fn rank(self, header: &str) -> (usize, String) {
+        match self {
+            GroupBy::Tag if header == NO_TAG => (1, String::new()),
+            GroupBy::Status => (
+                STATUSES
+                    .iter()
+                    .position(|status| *status == header)
+                    .unwrap_or(0),
+                String::new(),
+            ),
+            _ => (0, header.to_string()),
+        }
+    }
+}
+
+impl App {
+    /// The indices of the items of list `list_index` in the order they are
+    /// shown, skipping those hidden by the filter.
+    pub fn display_order(&self, list_index: usize) -> Vec<usize> {
+        let items = match self.group_list.items.get(list_index) {
+            Some(list) => &list.list.items,
+            None => return Vec::new(),
+        };
+        let mut order = (0..items.len())
+            .filter(|index| self.filter.matches(&items[*index]))
+            .collect::<Vec<_>>();
+        let group_by = self.group_by;
+        if group_by != GroupBy::None {
+            order.sort_by_cached_key(|index| group_by.rank(&group_by.header(&items[*index])));
+        }
+        order
+    }
+
+    /// The shown lines of list `list_index`, with a header before each group.
+    pub fn display_rows(&self, list_index: usize) -> Vec<Row> {
+        let items = &self.group_list.items[list_index].list.items;
+        let mut rows = Vec::new();
+        let mut current = None;
+        for index in self.display_order(list_index) {
+            if self.group_by != GroupBy::None {
+                let header = self.group_by.header(&items[index]);
+                if current.as_ref() != Some(&header) {
+                    current = Some(header.clone());
+                    rows.push(Row::Header(header));
+                }
+            }
+            rows.push(Row::Item(index));
+        }
+        rows
+    }
+
+    pub fn cycle_group_by(&mut self) {
+        self.group_by = self.group_by.next();
+    }
+}
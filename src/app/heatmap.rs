@@ -0,0 +1,102 @@
+use crate::app::app::{format_duration, App};
+use crate::app::due::start_of_week;
+
+use chrono::{Datelike, Duration, Local};
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Weeks shown, the current one last.
+const WEEKS: i64 = 12;
+/// Cells from no time to the busiest day of the period.
+const SHADES: [&str; 5] = ["··", "░░", "▒▒", "▓▓", "██"];
+
+impl App {
+    /// Adds `millis` of tracked time to today's total in the daily log.
+    pub fn log_day(&mut self, millis: i64) {
+        *self
+            .daily_log
+            .entry(Local::today().naive_local())
+            .or_insert(0) += millis;
+    }
+
+    pub fn process_heatmap(&mut self, key: KeyCode) {
+        if let KeyCode::Esc | KeyCode::Char('y') = key {
+            self.show_heatmap = false;
+        }
+    }
+
+    pub fn draw_heatmap<B: Backend>(&self, frame: &mut Frame<B>) {
+        if !self.show_heatmap {
+            return;
+        }
+        let size = frame.size();
+        let heatmap_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let today = Local::today().naive_local();
+        let first = start_of_week(today, self.config.week_start) - Duration::weeks(WEEKS - 1);
+        let logged = |offset: i64| {
+            let day = first + Duration::days(offset);
+            if day > today {
+                None
+            } else {
+                Some(self.daily_log.get(&day).copied().unwrap_or(0))
+            }
+        };
+        let days = (0..WEEKS * 7).filter_map(logged).collect::<Vec<_>>();
+        let max = days.iter().copied().max().unwrap_or(0);
+        let total = days.iter().sum::<i64>();
+
+        let mut text = Text::default();
+        for weekday in 0..7 {
+            let mut spans = vec![Span::raw(format!(
+                "{}  ",
+                (first + Duration::days(weekday)).weekday()
+            ))];
+            for week in 0..WEEKS {
+                let cell = match logged(week * 7 + weekday) {
+                    Some(millis) if millis > 0 && max > 0 => {
+                        let shade = ((millis * 4 + max - 1) / max).clamp(1, 4) as usize;
+                        Span::styled(SHADES[shade], Style::default().fg(Color::LightGreen))
+                    }
+                    Some(_) => Span::styled(SHADES[0], Style::default().fg(Color::Gray)),
+                    None => Span::raw("  "),
+                };
+                spans.push(cell);
+                spans.push(Span::raw(" "));
+            }
+            text.lines.push(Spans::from(spans));
+        }
+        text.lines.push(Spans::from(vec![Span::raw("")]));
+        text.lines.push(Spans::from(vec![Span::raw(format!(
+            "{} in {} weeks, busiest day {}",
+            format_duration(total, self.config.duration_precision),
+            WEEKS,
+            format_duration(max, self.config.duration_precision)
+        ))]));
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Activity · Esc: close ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left);
+
+        frame.render_widget(Clear, heatmap_size);
+        frame.render_widget(paragraph, heatmap_size);
+    }
+}
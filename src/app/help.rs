@@ -0,0 +1,265 @@
+use crate::app::app::App;
+
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+const HELP: &[(&str, &str)] = &[
+    ("Ctrl+n", "New list / new item"),
+    ("Alt+n", "Quick capture an item into the inbox list"),
+    ("Ctrl+e", "Edit selected item / settings of selected list"),
+    ("Ctrl+d", "Delete selected list / item"),
+    ("Ctrl+r", "Search and replace in all items"),
+    ("Ctrl+z", "Undo, puts back what was cut first"),
+    ("Ctrl+y", "Redo"),
+    ("b", "Show recently deleted lists / items"),
+    ("v", "Show running timers of all lists"),
+    ("j / Alt+j", "Jump to the item / list with the most time"),
+    ("Alt+k", "Cap durations at the time between start and end"),
+    ("Alt+v", "Show / hide a done bar after each list's name"),
+    ("l", "Show / hide how long open items were idle"),
+    ("g", "Show all tags, Enter renames one everywhere"),
+    ("n", "Open the scratchpad for free notes"),
+    (
+        "w",
+        "Review open items one by one: done, snooze, delete or skip",
+    ),
+    ("y", "Show a heatmap of the time tracked per day"),
+    (
+        "Ctrl+u",
+        "Show the totals as of a past day, e exports that week by day as CSV",
+    ),
+    (
+        "Ctrl+c",
+        "Pause / resume all timers at once, e.g. when away",
+    ),
+    (
+        "W",
+        "Plan the week: budget left per list and per remaining day",
+    ),
+    ("t", "Show today's focus items of all lists"),
+    ("q", "Show the agenda of open items by due date"),
+    (
+        "Ctrl+x",
+        "Swap the selected item with one from another list",
+    ),
+    ("Ctrl+g", "Show the recent messages"),
+    ("Ctrl+b", "Collapse / expand the selected list's totals"),
+    (
+        "Ctrl+f",
+        "Show elapsed / remaining time in rows of items with a target",
+    ),
+    (
+        "Ctrl+k",
+        "Timers of the selected item: Space start / stop, n new, x remove",
+    ),
+    ("a", "Add / remove selected item to / from today's focus"),
+    ("Right (Enter) / Left", "Enter / leave a list"),
+    (
+        "Left (no list open)",
+        "Back to the last place or hide the details, see left_at_top",
+    ),
+    (
+        "Right / Left (on an item)",
+        "Focus its subtasks, Space checks one / back to the items",
+    ),
+    (
+        "Enter (in a list)",
+        "Start / pause selected item, if enter_toggles_timer is set",
+    ),
+    (
+        "[ / ] (Ctrl+Left / Ctrl+Right)",
+        "Open previous / next list",
+    ),
+    ("Up / Down", "Select previous / next"),
+    (
+        "Ctrl+Up / Ctrl+Down",
+        "Move selected list / item, into the next list with move_across_lists",
+    ),
+    (
+        "Ctrl+Home / Ctrl+End",
+        "Move selected list / item to top / bottom",
+    ),
+    ("x / p", "Cut / paste selected list / item"),
+    ("r", "Rename selected list"),
+    ("m", "Merge selected list into another"),
+    ("h", "Hide / show done items"),
+    ("u", "Show / hide items scheduled for a later day"),
+    ("o", "Cycle sort order of the list"),
+    ("Alt+g", "Group the open list by tag / status / not at all"),
+    ("c", "Cycle the item card layout"),
+    ("k", "Toggle the to-do / in progress / done board"),
+    ("z", "Zoom into the open list (Left to leave)"),
+    ("f", "Freeze / unfreeze the displayed times"),
+    ("Ctrl+p", "Pin / unpin selected item"),
+    ("Alt+l", "Lock / unlock selected item"),
+    ("Alt+a", "Add a subtask to selected item"),
+    ("Alt+m", "Set / remove a metadata field of selected item"),
+    ("Alt+x", "Split time of selected item into a new item"),
+    ("$", "Mark selected item billable / not billable"),
+    ("B", "Mark selected item blocked / unblocked"),
+    ("> / <", "Raise / lower progress of selected item by 10%"),
+    (
+        "+ / -",
+        "Move due date of selected item a day later / earlier",
+    ),
+    ("Alt+c / Alt+u", "Mark all subtasks done / not done"),
+    ("Alt+e", "Turn selected item into a list of its subtasks"),
+    ("Alt+o", "Open the link of selected item"),
+    (
+        "Alt+s",
+        "Start, then pause / resume timer, reset if done (on a list: its first open item)",
+    ),
+    ("Alt+r", "Reset timer of selected item"),
+    ("Alt+d", "Mark done / not done"),
+    ("Alt+p", "Pause / resume timer"),
+    (
+        "Alt+q",
+        "Let paused timers keep counting / stop them counting",
+    ),
+    (
+        "Alt+t",
+        "Pause all running timers of the list / resume them",
+    ),
+    ("Alt+b", "Timebox the list, Esc stops it"),
+    ("Tab", "Jump to the next running item"),
+    ("Alt+y", "Copy today's time per list to the clipboard"),
+    (
+        "i",
+        "Import item titles from the import file, with a preview",
+    ),
+    (
+        "Ctrl+o",
+        "Import items from the CSV file, adding to or replacing the lists",
+    ),
+    ("s", "Write selected item as a template to the snippet file"),
+    (
+        "Alt+i",
+        "Add the item from the snippet file to the open list",
+    ),
+    (
+        "s / Alt+i (on lists)",
+        "Copy selected list as a template / paste one from the clipboard",
+    ),
+    (
+        "e",
+        "Write the report of the open list, or all lists, to a file",
+    ),
+    (
+        "d",
+        "Write today's done and still open items to a dated note",
+    ),
+    (
+        "Space",
+        "Cycle selected item: not started, running, paused, done",
+    ),
+    ("Alt+z", "Write the screen as text to the snapshot file"),
+    ("Alt+f", "Show only the running item and its time"),
+    ("Alt+h", "Switch between 24-hour and 12-hour times"),
+    ("Alt+w", "Save and switch to the next workspace"),
+    ("?", "Show / hide this help"),
+    ("Ctrl+l", "Clear and redraw the screen"),
+    ("Ctrl+s", "Save now"),
+    (
+        "Ctrl+q",
+        "Save and quit, confirmed with y unless confirm_quit is off",
+    ),
+];
+
+impl App {
+    /// Scrolls the help, any other key closes it.
+    pub fn process_help(&mut self, key: KeyCode) {
+        let last = HELP.len() as u16 - 1;
+        self.help_scroll = match key {
+            KeyCode::Up => self.help_scroll.saturating_sub(1),
+            KeyCode::Down => (self.help_scroll + 1).min(last),
+            KeyCode::PageUp => self.help_scroll.saturating_sub(10),
+            KeyCode::PageDown => (self.help_scroll + 10).min(last),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            _ => {
+                self.show_help = false;
+                return;
+            }
+        };
+    }
+
+    pub fn draw_help<B: Backend>(&self, frame: &mut Frame<B>) {
+        let size = frame.size();
+        let help_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let key_width = HELP.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+        let mut text = Text::default();
+        for (key, action) in HELP {
+            text.lines.push(Spans::from(vec![
+                Span::styled(
+                    format!("{:<width$}  ", key, width = key_width),
+                    Style::default().fg(Color::LightCyan),
+                ),
+                Span::raw(*action),
+            ]));
+        }
+
+        // Count the lines after wrapping so the end stays at the bottom.
+        let width = help_size.width.saturating_sub(2).max(1) as usize;
+        let lines = HELP
+            .iter()
+            .map(|(_, action)| (key_width + 2 + action.width()).saturating_sub(1) / width + 1)
+            .sum::<usize>() as u16;
+        let height = help_size.height.saturating_sub(2);
+        let top = self.help_scroll.min(lines.saturating_sub(height));
+
+        let help = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Help · Up / Down: scroll ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true })
+            .scroll((top, 0));
+
+        frame.render_widget(Clear, help_size);
+        frame.render_widget(help, help_size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn arrows_scroll_the_help_and_other_keys_close_it() {
+        let mut app = App::new("Test".to_string());
+        app.event(KeyCode::Char('?'), KeyModifiers::NONE);
+        app.event(KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(app.help_scroll, 0);
+        app.event(KeyCode::PageDown, KeyModifiers::NONE);
+        app.event(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.help_scroll, 11);
+        app.event(KeyCode::End, KeyModifiers::NONE);
+        app.event(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.help_scroll as usize, HELP.len() - 1);
+        assert!(app.show_help);
+
+        app.event(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.show_help);
+        app.event(KeyCode::Char('?'), KeyModifiers::NONE);
+        assert_eq!(app.help_scroll, 0);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::app::app::{format_duration, App, Item};
+use crate::app::config::DurationPrecision;
+
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Wraps a value in single quotes so the shell takes it literally.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Replaces each `{name}` of `values` in one pass, so placeholders inside
+/// the values themselves stay as they are.
+fn fill_placeholders(template: &str, values: &[(&str, String)]) -> String {
+    let mut output = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let placeholder = values.iter().find(|(name, _)| {
+            rest[1..].starts_with(name) && rest[1 + name.len()..].starts_with('}')
+        });
+        match placeholder {
+            Some((name, value)) => {
+                output.push_str(value);
+                rest = &rest[name.len() + 2..];
+            }
+            None => {
+                output.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+fn done_command(
+    template: &str,
+    list_name: &str,
+    item: &Item,
+    precision: DurationPrecision,
+) -> String {
+    fill_placeholders(
+        template,
+        &[
+            ("title", shell_quote(&item.title)),
+            (
+                "duration",
+                shell_quote(&format_duration(item.duration, precision)),
+            ),
+            ("list", shell_quote(list_name)),
+        ],
+    )
+}
+
+impl App {
+    /// Runs the configured `on_done_command` for an item that was just marked
+    /// done, without waiting for it to finish.
+    pub fn run_done_hook(&mut self, list_name: &str, item: &Item) {
+        let template = match &self.config.on_done_command {
+            Some(template) if !template.trim().is_empty() => template,
+            _ => return,
+        };
+        let command = done_command(template, list_name, item, self.config.duration_precision);
+
+        let spawned = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(mut child) => {
+                thread::spawn(move || child.wait());
+            }
+            Err(err) => {
+                self.notify(format!("Could not run on_done_command: {}", err));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_in_values_are_not_replaced_again() {
+        let item = Item {
+            title: "Say \"hi\" to {list} and 'bye'".to_string(),
+            ..Item::default()
+        };
+        let command = done_command(
+            "notify {title} {list} {unknown} {",
+            "Work",
+            &item,
+            DurationPrecision::default(),
+        );
+        assert_eq!(
+            command,
+            "notify 'Say \"hi\" to {list} and '\\''bye'\\''' 'Work' {unknown} {"
+        );
+    }
+
+    #[test]
+    fn every_placeholder_is_filled() {
+        let item = Item {
+            title: "Task".to_string(),
+            ..Item::default()
+        };
+        let command = done_command(
+            "{title}{title} {duration} {list}",
+            "{title}",
+            &item,
+            DurationPrecision::default(),
+        );
+        assert!(command.starts_with("'Task''Task' '"));
+        assert!(command.ends_with(" '{title}'"));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::app::app::{App, Item};
+
+use crossterm::event::KeyCode;
+use std::fs;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Titles read from the import file, waiting to be confirmed.
+pub struct ImportPreview {
+    pub list_index: usize,
+    pub titles: Vec<String>,
+}
+
+impl App {
+    /// Reads one item per line from the import file into the open or
+    /// selected list. Blank lines and list markers like "- " are dropped.
+    pub fn start_import(&mut self) {
+        let list_index = match self
+            .active_list
+            .or_else(|| self.group_list.state.selected())
+        {
+            Some(index) => index,
+            None => {
+                self.notify("Select a list to import into".to_string());
+                return;
+            }
+        };
+        let path = &self.config.import_file;
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) => {
+                self.notify(format!("Could not read {}: {}", path, err));
+                return;
+            }
+        };
+        let titles = content
+            .lines()
+            .map(|line| {
+                line.trim()
+                    .trim_start_matches("[ ] ")
+                    .trim_start_matches("- ")
+                    .trim_start_matches("* ")
+                    .trim()
+                    .to_string()
+            })
+            .filter(|title| !title.is_empty())
+            .collect::<Vec<_>>();
+        if titles.is_empty() {
+            self.notify(format!("Nothing to import in {}", path));
+            return;
+        }
+        self.import = Some(ImportPreview { list_index, titles });
+    }
+
+    pub fn process_import(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Enter => {
+                if let Some(preview) = self.import.take() {
+                    let count = preview.titles.len();
+                    for title in preview.titles {
+                        let item = Item {
+                            id: self.new_id(),
+                            title,
+                            ..Item::default()
+                        };
+                        if let Some(list) = self.group_list.items.get_mut(preview.list_index) {
+                            list.list.add(item);
+                        }
+                    }
+                    self.notify(format!("Imported {} items", count));
+                }
+            }
+            KeyCode::Esc => {
+                self.import = None;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw_import<B: Backend>(&self, frame: &mut Frame<B>) {
+        let preview = match &self.import {
+            Some(preview) => preview,
+            None => return,
+        };
+        let size = frame.size();
+        let preview_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let list_name = self
+            .group_list
+            .items
+            .get(preview.list_index)
+            .map(|list| list.name.as_str())
+            .unwrap_or_default();
+        let mut text = Text::from(Spans::from(vec![Span::styled(
+            format!(
+                "{} items from {} into \"{}\"",
+                preview.titles.len(),
+                self.config.import_file,
+                list_name
+            ),
+            Style::default().fg(Color::LightCyan),
+        )]));
+        for title in &preview.titles {
+            text.lines
+                .push(Spans::from(vec![Span::raw(format!("  {}", title))]));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Import · Enter: add items · Esc: cancel ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, preview_size);
+        frame.render_widget(paragraph, preview_size);
+    }
+}
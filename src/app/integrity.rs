@@ -0,0 +1,124 @@
+use crate::app::app::App;
+
+use std::collections::HashSet;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+impl App {
+    /// Fixes what a hand-edited or damaged database can get wrong and
+    /// describes each fix. Runs before the selections are clamped.
+    pub fn validate_and_repair(&mut self) -> Vec<String> {
+        let mut repairs = Vec::new();
+
+        let max_id = self
+            .group_list
+            .items
+            .iter()
+            .flat_map(|list| list.list.items.iter())
+            .map(|item| item.id)
+            .max()
+            .unwrap_or(0);
+        if self.next_id < max_id {
+            repairs.push(format!(
+                "Next id {} was below used id {}",
+                self.next_id, max_id
+            ));
+            self.next_id = max_id;
+        }
+
+        let mut ids = HashSet::new();
+        let mut next_id = self.next_id;
+        for list in &mut self.group_list.items {
+            let name = list.name.clone();
+            for item in &mut list.list.items {
+                let label = format!("\"{}\" in \"{}\"", item.title, name);
+                if item.id != 0 && !ids.insert(item.id) {
+                    next_id += 1;
+                    repairs.push(format!(
+                        "{}: duplicate id {}, now {}",
+                        label, item.id, next_id
+                    ));
+                    item.id = next_id;
+                }
+                if item.duration < 0 {
+                    repairs.push(format!("{}: negative duration set to 0", label));
+                    item.duration = 0;
+                }
+                if item.tracked_today < 0 {
+                    repairs.push(format!("{}: negative time today set to 0", label));
+                    item.tracked_today = 0;
+                }
+                if item.progress > 100 {
+                    repairs.push(format!(
+                        "{}: progress {}% set to 100%",
+                        label, item.progress
+                    ));
+                    item.progress = 100;
+                }
+                if let (Some(start_at), Some(end_at)) = (item.start_at, item.end_at) {
+                    if end_at < start_at {
+                        repairs.push(format!("{}: ended before it started, start moved", label));
+                        item.start_at = Some(end_at);
+                    }
+                }
+            }
+            if let Some(selected) = list.list.state.selected() {
+                if selected >= list.list.items.len() {
+                    repairs.push(format!("\"{}\": selection out of range", name));
+                }
+            }
+        }
+        self.next_id = next_id;
+
+        if let Some(selected) = self.group_list.state.selected() {
+            if selected >= self.group_list.items.len() {
+                repairs.push("List selection out of range".to_string());
+            }
+        }
+        if let Some(active) = self.active_list {
+            if active >= self.group_list.items.len() {
+                repairs.push("Open list out of range".to_string());
+            }
+        }
+        repairs
+    }
+
+    pub fn draw_repairs<B: Backend>(&self, frame: &mut Frame<B>) {
+        if self.repairs.is_empty() {
+            return;
+        }
+        let size = frame.size();
+        let repairs_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let text = Text::from(
+            self.repairs
+                .iter()
+                .map(|repair| Spans::from(vec![Span::raw(format!("- {}", repair))]))
+                .collect::<Vec<_>>(),
+        );
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Repaired on load · any key: close ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, repairs_size);
+        frame.render_widget(paragraph, repairs_size);
+    }
+}
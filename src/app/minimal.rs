@@ -0,0 +1,95 @@
+use crate::app::app::{format_duration, App};
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+impl App {
+    /// The item shown in the minimal view: the selected one if it is in
+    /// progress, otherwise the first running one.
+    fn minimal_item(&self) -> Option<(usize, usize)> {
+        self.selected_item()
+            .filter(|(list_index, index)| {
+                let item = &self.group_list.items[*list_index].list.items[*index];
+                item.started() && !item.done()
+            })
+            .or_else(|| self.running_positions().first().copied())
+    }
+
+    /// Whether the minimal view takes over the screen.
+    pub fn minimal_shown(&self) -> bool {
+        self.minimal && self.minimal_item().is_some()
+    }
+
+    /// Swallows every key but the toggle, pause and done while the minimal
+    /// view is shown. The latter two are pointed at the shown item.
+    pub fn minimal_ignores(&mut self, key: KeyCode, modi: KeyModifiers) -> bool {
+        if !self.minimal_shown() {
+            return false;
+        }
+        match (key, modi) {
+            (KeyCode::Char('f'), KeyModifiers::ALT) => false,
+            (KeyCode::Char('p'), KeyModifiers::ALT) | (KeyCode::Char('d'), KeyModifiers::ALT) => {
+                if let Some((list_index, index)) = self.minimal_item() {
+                    self.focus_item(list_index, index);
+                }
+                false
+            }
+            _ => true,
+        }
+    }
+
+    pub fn draw_minimal<B: Backend>(&self, frame: &mut Frame<B>) {
+        let (list_index, index) = match self.minimal_item() {
+            Some(position) => position,
+            None => return,
+        };
+        let item = self.shown(&self.group_list.items[list_index].list.items[index]);
+        let size = frame.size();
+
+        let paused = if item.paused { " (paused)" } else { "" };
+        let text = Text::from(vec![
+            Spans::from(vec![Span::styled(
+                item.title.clone(),
+                Style::default().fg(Color::Gray),
+            )]),
+            Spans::from(vec![]),
+            Spans::from(vec![Span::styled(
+                format!(
+                    "{}{}",
+                    format_duration(item.duration, self.config.duration_precision),
+                    paused
+                ),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )]),
+            Spans::from(vec![]),
+            Spans::from(vec![Span::styled(
+                "Alt+p: pause · Alt+d: done · Alt+f: leave",
+                Style::default().fg(Color::DarkGray),
+            )]),
+        ]);
+
+        let height = (text.lines.len() as u16).min(size.height);
+        let area = Rect::new(
+            size.x,
+            size.y + (size.height - height) / 2,
+            size.width,
+            height,
+        );
+        let background = Paragraph::new(Text::default()).style(Style::default().bg(Color::Black));
+        let paragraph = Paragraph::new(text)
+            .style(Style::default().bg(Color::Black))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(background, size);
+        frame.render_widget(paragraph, area);
+    }
+}
@@ -1,2 +1,38 @@
+pub mod activity;
+#[allow(clippy::module_inception)]
 pub mod app;
+pub mod as_of;
+pub mod bin;
+pub mod board;
+pub mod clipboard;
+pub mod config;
+pub mod confirm;
+pub mod csv_import;
+pub mod due;
+pub mod freeze;
+pub mod group_by;
+pub mod heatmap;
+pub mod help;
+pub mod hook;
+pub mod import;
+pub mod integrity;
+pub mod minimal;
+pub mod onboarding;
+pub mod overview;
+pub mod planning;
+pub mod replace;
+pub mod report;
+pub mod review;
+pub mod scratchpad;
+pub mod snapshot;
+pub mod snippet;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod stateful_list;
+pub mod status;
+pub mod subtasks;
+pub mod tags;
+pub mod timebox;
+pub mod timers;
+pub mod undo;
+pub mod workspace;
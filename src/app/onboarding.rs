@@ -0,0 +1,73 @@
+use crate::app::app::App;
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+const STEPS: [(&str, &str); 6] = [
+    ("Ctrl+n", "Create a list, for a project or a client"),
+    ("Right / Enter", "Enter the selected list"),
+    ("Ctrl+n", "Add an item to the list you are in"),
+    ("Alt+s", "Start the selected item's timer, Alt+p pauses it"),
+    ("Left", "Go back to the lists"),
+    ("?", "Show all keys"),
+];
+
+impl App {
+    /// Closes the overlay for good, it is only shown on the first launch.
+    pub fn dismiss_onboarding(&mut self) {
+        self.show_onboarding = false;
+        self.seen_onboarding = true;
+    }
+
+    pub fn draw_onboarding<B: Backend>(&self, frame: &mut Frame<B>) {
+        if !self.show_onboarding {
+            return;
+        }
+        let size = frame.size();
+        let onboarding_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let key_width = STEPS.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+        let mut text = Text::from(vec![
+            Spans::from(vec![Span::raw(
+                "Todo-Timer keeps lists of items and tracks the time spent on each.",
+            )]),
+            Spans::from(vec![Span::raw("")]),
+        ]);
+        for (key, action) in STEPS.iter() {
+            text.lines.push(Spans::from(vec![
+                Span::styled(
+                    format!("{:>width$}  ", key, width = key_width),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(*action),
+            ]));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Welcome · any key: start ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, onboarding_size);
+        frame.render_widget(paragraph, onboarding_size);
+    }
+}
@@ -0,0 +1,249 @@
+use crate::app::app::{App, Item};
+
+use chrono::{Datelike, Duration, Local};
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Items gathered from all lists into one panel.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Overview {
+    Running,
+    /// Items marked as today's focus.
+    Today,
+    /// Open items with a due date, soonest first.
+    Agenda,
+    /// Items of the other lists, to swap with the item at `index` of
+    /// `list_index`.
+    Swap {
+        list_index: usize,
+        index: usize,
+    },
+}
+
+impl Overview {
+    fn key(self) -> char {
+        match self {
+            Overview::Running => 'v',
+            Overview::Today => 't',
+            Overview::Agenda => 'q',
+            Overview::Swap { .. } => 'x',
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Overview::Running => " Running timers · Enter: jump to item · Esc: close ",
+            Overview::Today => " Today's focus · Enter: jump to item · Esc: close ",
+            Overview::Agenda => " Agenda · Enter: jump to item · Esc: close ",
+            Overview::Swap { .. } => " Swap with · Enter: swap · Esc: cancel ",
+        }
+    }
+
+    fn empty(self) -> &'static str {
+        match self {
+            Overview::Running => "No timers running",
+            Overview::Today => "Nothing focused today, press a on an item to add it",
+            Overview::Agenda => "No open items with a due date",
+            Overview::Swap { .. } => "No items in other lists",
+        }
+    }
+
+    fn matches(self, item: &Item) -> bool {
+        match self {
+            Overview::Running => item.running(),
+            Overview::Today => item.focused_today(),
+            Overview::Agenda => item.due_at.is_some() && !item.done(),
+            Overview::Swap { .. } => !item.locked,
+        }
+    }
+}
+
+impl App {
+    /// List and item positions of all running items, in list order.
+    pub fn running_positions(&self) -> Vec<(usize, usize)> {
+        self.overview_positions(Overview::Running)
+    }
+
+    fn overview_positions(&self, overview: Overview) -> Vec<(usize, usize)> {
+        let mut positions = self
+            .group_list
+            .items
+            .iter()
+            .enumerate()
+            .flat_map(|(list_index, list)| {
+                list.list
+                    .items
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, item)| overview.matches(item))
+                    .map(move |(index, _)| (list_index, index))
+            })
+            .collect::<Vec<_>>();
+        if let Overview::Swap { list_index, .. } = overview {
+            positions.retain(|(other, _)| *other != list_index);
+        }
+        if overview == Overview::Agenda {
+            positions.sort_by_key(|(list_index, index)| {
+                self.group_list.items[*list_index].list.items[*index].due_at
+            });
+        }
+        positions
+    }
+
+    /// The day header an agenda item goes under.
+    fn agenda_day(&self, item: &Item) -> String {
+        let now = Local::now();
+        let due_at = match item.due_at {
+            Some(due_at) => due_at,
+            None => return String::new(),
+        };
+        let today = now.date().naive_local();
+        let day = due_at.date().naive_local();
+        if due_at < now {
+            "Overdue".to_string()
+        } else if day == today {
+            "Today".to_string()
+        } else if day == today + Duration::days(1) {
+            "Tomorrow".to_string()
+        } else if self.config.show_week_numbers {
+            format!(
+                "{} W{:02} {}",
+                day.weekday(),
+                day.iso_week().week(),
+                day.format("%Y-%m-%d")
+            )
+        } else {
+            day.format("%a %Y-%m-%d").to_string()
+        }
+    }
+
+    pub fn process_overview(&mut self, key: KeyCode) {
+        let (overview, row) = match self.overview {
+            Some(overview) => overview,
+            None => return,
+        };
+        let positions = self.overview_positions(overview);
+        // The selection may point past the end once an item dropped out.
+        let row = row.min(positions.len().saturating_sub(1));
+        match key {
+            KeyCode::Esc => {
+                self.overview = None;
+            }
+            KeyCode::Char(c) if c == overview.key() => {
+                self.overview = None;
+            }
+            KeyCode::Up => {
+                self.overview = Some((overview, row.saturating_sub(1)));
+            }
+            KeyCode::Down if row + 1 < positions.len() => {
+                self.overview = Some((overview, row + 1));
+            }
+            KeyCode::Enter => {
+                if let Some((list_index, index)) = positions.get(row).copied() {
+                    self.overview = None;
+                    match overview {
+                        Overview::Swap {
+                            list_index: from_list,
+                            index: from,
+                        } => self.swap_items((from_list, from), (list_index, index)),
+                        _ => self.focus_item(list_index, index),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw_overview<B: Backend>(&self, frame: &mut Frame<B>) {
+        let (overview, row) = match self.overview {
+            Some(overview) => overview,
+            None => return,
+        };
+        let size = frame.size();
+        let overview_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let positions = self.overview_positions(overview);
+        let mut text = Text::default();
+        if positions.is_empty() {
+            text.lines
+                .push(Spans::from(vec![Span::raw(overview.empty())]));
+        }
+        let selected = row.min(positions.len().saturating_sub(1));
+        let mut day = None;
+        for (row, (list_index, index)) in positions.into_iter().enumerate() {
+            let list = &self.group_list.items[list_index];
+            let item = self.shown(&list.list.items[index]);
+            let style = if selected == row {
+                Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            let (first, first_style) = if overview == Overview::Agenda {
+                let header = self.agenda_day(&item);
+                let overdue = header == "Overdue";
+                let color = if overdue {
+                    Color::Red
+                } else {
+                    Color::LightCyan
+                };
+                if day.as_ref() != Some(&header) {
+                    text.lines.push(Spans::from(vec![Span::styled(
+                        header.clone(),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    )]));
+                    day = Some(header);
+                }
+                // Overdue items can be from any day, so they keep their date.
+                let format = if overdue {
+                    format!("%m-%d {}", self.config.clock_format.time())
+                } else {
+                    self.config.clock_format.time().to_string()
+                };
+                let due = item
+                    .due_at
+                    .map(|due_at| self.config.timestamp(due_at, &format))
+                    .unwrap_or_default();
+                (due, Style::default().fg(color))
+            } else {
+                (
+                    item.formatted_duration(self.config.duration_precision),
+                    Style::default().fg(Color::LightCyan),
+                )
+            };
+            text.lines.push(Spans::from(vec![
+                Span::styled(format!("{:>10}  ", first), first_style),
+                Span::styled(
+                    format!("{}  ", list.name),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(item.title, style),
+            ]));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(overview.title())
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, overview_size);
+        frame.render_widget(paragraph, overview_size);
+    }
+}
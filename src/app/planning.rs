@@ -0,0 +1,130 @@
+use crate::app::app::{format_hours, App, GroupList, Item};
+use crate::app::as_of::duration_at;
+use crate::app::due::start_of_week;
+
+use chrono::{Duration, Local, NaiveDate, TimeZone};
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Time logged on the list since the week started.
+fn logged_this_week(list: &GroupList<Item>, week_start: NaiveDate) -> i64 {
+    let since = Local
+        .from_local_datetime(&week_start.and_hms(0, 0, 0))
+        .single()
+        .unwrap_or_else(Local::now);
+    list.list
+        .items
+        .iter()
+        .map(|item| (item.duration - duration_at(item, since).0).max(0))
+        .sum()
+}
+
+impl App {
+    pub fn process_planning(&mut self, key: KeyCode) {
+        if let KeyCode::Esc = key {
+            self.show_planning = false;
+        }
+    }
+
+    pub fn draw_planning<B: Backend>(&self, frame: &mut Frame<B>) {
+        if !self.show_planning {
+            return;
+        }
+        let size = frame.size();
+        let planning_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let today = Local::today().naive_local();
+        let week_start = start_of_week(today, self.config.week_start);
+        // Today counts as a day left, the week ends before the next start.
+        let days_left = (week_start + Duration::weeks(1) - today).num_days();
+
+        let mut text = Text::from(vec![Spans::from(vec![Span::styled(
+            format!(
+                "Week of {} · {} day{} left",
+                week_start.format("%a %Y-%m-%d"),
+                days_left,
+                if days_left == 1 { "" } else { "s" }
+            ),
+            Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+        )])]);
+        let budgeted = self
+            .group_list
+            .items
+            .iter()
+            .filter_map(|list| list.weekly_budget.map(|budget| (list, budget)))
+            .collect::<Vec<_>>();
+        if budgeted.is_empty() {
+            text.lines.push(Spans::from(vec![Span::raw("")]));
+            text.lines.push(Spans::from(vec![Span::styled(
+                "No list has a weekly budget, set one in the list settings",
+                Style::default().fg(Color::Gray),
+            )]));
+        }
+        for (list, budget) in budgeted {
+            let budget = Duration::minutes(budget).num_milliseconds();
+            let logged = logged_this_week(list, week_start);
+            let left = budget - logged;
+            let (plan, color) = if left > 0 {
+                (
+                    format!(
+                        "{} left, ~{}/day over {} day{}",
+                        format_hours(left),
+                        format_hours(left / days_left),
+                        days_left,
+                        if days_left == 1 { "" } else { "s" }
+                    ),
+                    Color::White,
+                )
+            } else if left == 0 {
+                ("budget used up".to_string(), Color::LightGreen)
+            } else {
+                (
+                    format!("{} over budget", format_hours(-left)),
+                    Color::LightRed,
+                )
+            };
+            text.lines.push(Spans::from(vec![Span::raw("")]));
+            text.lines.push(Spans::from(vec![Span::styled(
+                format!(
+                    "{}  {} of {}",
+                    list.name,
+                    format_hours(logged),
+                    format_hours(budget)
+                ),
+                Style::default().fg(Color::Yellow),
+            )]));
+            text.lines.push(Spans::from(vec![Span::styled(
+                format!("  {}", plan),
+                Style::default().fg(color),
+            )]));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Week plan · Esc: close ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, planning_size);
+        frame.render_widget(paragraph, planning_size);
+    }
+}
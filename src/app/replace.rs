@@ -0,0 +1,122 @@
+use crate::app::app::App;
+use crate::app::undo::Operation;
+
+/// Finds the byte range of the first match of `needle`, comparing characters
+/// by their lowercase form.
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<(usize, usize)> {
+    haystack.char_indices().find_map(|(start, _)| {
+        let mut rest = haystack[start..].char_indices();
+        let mut end = start;
+        for n in needle.chars() {
+            match rest.next() {
+                Some((offset, c)) if c.to_lowercase().eq(n.to_lowercase()) => {
+                    end = start + offset + c.len_utf8();
+                }
+                _ => return None,
+            }
+        }
+        Some((start, end))
+    })
+}
+
+fn replace_in(text: &mut String, search: &str, replacement: &str, case_sensitive: bool) -> usize {
+    if case_sensitive {
+        let count = text.matches(search).count();
+        if count > 0 {
+            *text = text.replace(search, replacement);
+        }
+        return count;
+    }
+
+    let mut count = 0;
+    let mut output = String::new();
+    let mut rest = text.as_str();
+    while let Some((start, end)) = find_ignore_case(rest, search) {
+        output.push_str(&rest[..start]);
+        output.push_str(replacement);
+        rest = &rest[end..];
+        count += 1;
+    }
+    if count > 0 {
+        output.push_str(rest);
+        *text = output;
+    }
+    count
+}
+
+impl App {
+    /// Replaces `search` in the title and description of every unlocked
+    /// item and returns the number of replacements made. Each changed item
+    /// can be undone on its own.
+    pub fn replace_all(&mut self, search: &str, replacement: &str, case_sensitive: bool) -> usize {
+        if search.is_empty() {
+            return 0;
+        }
+
+        let mut count = 0;
+        let mut edits = Vec::new();
+        for (list_index, list) in self.group_list.items.iter_mut().enumerate() {
+            for item in list.list.items.iter_mut().filter(|item| !item.locked) {
+                let previous = item.clone();
+                let replaced = replace_in(&mut item.title, search, replacement, case_sensitive)
+                    + replace_in(&mut item.desc, search, replacement, case_sensitive);
+                if replaced > 0 {
+                    count += replaced;
+                    edits.push(Operation::Edit {
+                        list_index,
+                        item: previous,
+                    });
+                }
+            }
+        }
+        for edit in edits {
+            self.record(edit);
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::app::app_with_items;
+
+    fn titles(app: &App) -> Vec<&str> {
+        app.group_list.items[0]
+            .list
+            .items
+            .iter()
+            .map(|item| item.title.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn replacing_ignores_case_when_asked() {
+        let mut text = "Acme and ACME".to_string();
+        assert_eq!(replace_in(&mut text, "acme", "Globex", true), 0);
+        assert_eq!(replace_in(&mut text, "acme", "Globex", false), 2);
+        assert_eq!(text, "Globex and Globex");
+    }
+
+    #[test]
+    fn locked_items_are_left_alone() {
+        let mut app = app_with_items(&["Acme call", "Acme invoice"]);
+        app.group_list.items[0].list.items[1].locked = true;
+        assert_eq!(app.replace_all("Acme", "Globex", true), 1);
+        assert_eq!(titles(&app), vec!["Globex call", "Acme invoice"]);
+    }
+
+    #[test]
+    fn each_replaced_item_can_be_undone() {
+        let mut app = app_with_items(&["Acme call", "Other", "Acme"]);
+        app.group_list.items[0].list.items[2].desc = "for Acme".to_string();
+        assert_eq!(app.replace_all("Acme", "Globex", true), 3);
+        assert_eq!(app.undo.len(), 2);
+
+        app.undo();
+        assert_eq!(titles(&app), vec!["Globex call", "Other", "Acme"]);
+        assert_eq!(app.group_list.items[0].list.items[2].desc, "for Acme");
+        app.undo();
+        assert_eq!(titles(&app), vec!["Acme call", "Other", "Acme"]);
+    }
+}
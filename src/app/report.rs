@@ -0,0 +1,375 @@
+use crate::app::app::{format_duration, format_hours, format_iso8601, App, GroupList, Item};
+
+use chrono::{Duration, Local, NaiveDate};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+
+impl App {
+    pub fn report(&self) -> String {
+        let mut report = self.report_lists(&self.group_list.items.iter().collect::<Vec<_>>());
+        report.push_str(&self.tag_report());
+        report
+    }
+
+    /// Time per tag below the lists, empty without tags.
+    fn tag_report(&self) -> String {
+        let durations = self.tag_durations();
+        let tag_width = durations
+            .keys()
+            .map(|tag| tag.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut output = String::new();
+        if !durations.is_empty() {
+            output.push_str("\nBy tag\n");
+        }
+        for (tag, duration) in durations {
+            output.push_str(&format!(
+                "{:<width$}  {}\n",
+                tag,
+                format_duration(duration, self.config.duration_precision),
+                width = tag_width
+            ));
+        }
+        output
+    }
+
+    /// The report limited to the list called `name`.
+    pub fn list_report(&self, name: &str) -> Option<String> {
+        let list = self
+            .group_list
+            .items
+            .iter()
+            .find(|list| list.name == name)?;
+        Some(self.report_lists(&[list]))
+    }
+
+    /// Writes the report of the active list to a file named after it, or of
+    /// all lists to `report.txt`, and returns the file name.
+    pub fn export_report(&self) -> Result<String, Box<dyn Error>> {
+        let (path, report) = match self
+            .active_list
+            .and_then(|index| self.group_list.items.get(index))
+        {
+            Some(list) => (
+                format!("{}.txt", file_name(&list.name)),
+                self.report_lists(&[list]),
+            ),
+            None => ("report.txt".to_string(), self.report()),
+        };
+        fs::write(&path, report)?;
+        Ok(path)
+    }
+
+    fn report_lists(&self, lists: &[&GroupList<Item>]) -> String {
+        let total = lists.iter().map(|list| list.total_duration()).sum();
+        let billable = lists.iter().map(|list| list.billable_duration()).sum();
+        let longest_label = if billable == total {
+            "Total"
+        } else {
+            "Billable"
+        };
+        let name_width = lists
+            .iter()
+            .map(|list| list.name.chars().count())
+            .chain(std::iter::once(longest_label.len()))
+            .max()
+            .unwrap_or(0);
+        let duration_width = lists
+            .iter()
+            .map(|list| list.total_duration())
+            .chain(std::iter::once(total))
+            .map(|duration| {
+                format_duration(duration, self.config.duration_precision)
+                    .chars()
+                    .count()
+            })
+            .max()
+            .unwrap_or(0);
+
+        let iso_width = lists
+            .iter()
+            .map(|list| format_iso8601(list.total_duration()).len())
+            .max()
+            .unwrap_or(0);
+
+        let mut output = String::new();
+        for list in lists {
+            let mut cost = list
+                .formatted_cost(self.config.billing_increment)
+                .map(|cost| format!("  {}", cost))
+                .unwrap_or_default();
+            if let Some(rounded) = list.rounded_duration() {
+                cost.push_str(&format!("  {}", format_hours(rounded)));
+            }
+            if let Some(code) = &list.project_code {
+                cost.push_str(&format!("  [{}]", code));
+            }
+            output.push_str(&format!(
+                "{:<width$}  {:<duration_width$}  {:<iso_width$}{}\n",
+                list.name,
+                format_duration(list.total_duration(), self.config.duration_precision),
+                format_iso8601(list.total_duration()),
+                cost,
+                width = name_width,
+                duration_width = duration_width,
+                iso_width = if cost.is_empty() { 0 } else { iso_width }
+            ));
+        }
+
+        output.push_str(&format!(
+            "{}\n",
+            "-".repeat(name_width + duration_width + 4)
+        ));
+        output.push_str(&format!(
+            "{:<width$}  {:<duration_width$}  {}\n",
+            "Total",
+            format_duration(total, self.config.duration_precision),
+            format_iso8601(total),
+            width = name_width,
+            duration_width = duration_width
+        ));
+        if billable != total {
+            output.push_str(&format!(
+                "{:<width$}  {:<duration_width$}  {}\n",
+                "Billable",
+                format_duration(billable, self.config.duration_precision),
+                format_iso8601(billable),
+                width = name_width,
+                duration_width = duration_width
+            ));
+        }
+        output
+    }
+
+    /// Markdown of the items finished on `day` and the open ones carried
+    /// over to the next, with the list each belongs to.
+    pub fn daily_note(&self, day: NaiveDate) -> String {
+        let precision = self.config.duration_precision;
+        let mut done = String::new();
+        let mut open = String::new();
+        let mut tags = String::new();
+        for (tag, duration) in self.tag_durations() {
+            tags.push_str(&format!(
+                "- {}: {}\n",
+                tag,
+                format_duration(duration, precision)
+            ));
+        }
+        for list in &self.group_list.items {
+            for item in list.list.items.iter().filter(|item| item.is_task()) {
+                match item.end_at {
+                    Some(end_at) if end_at.date().naive_local() == day => {
+                        let code = list
+                            .project_code_of(item)
+                            .map(|code| format!(" [{}]", code))
+                            .unwrap_or_default();
+                        done.push_str(&format!(
+                            "- [x] {} ({}){} {}\n",
+                            item.title,
+                            list.name,
+                            code,
+                            format_duration(item.duration, precision)
+                        ));
+                    }
+                    None if !item.scheduled() => {
+                        let so_far = if item.started() {
+                            format!(" {} so far", format_duration(item.duration, precision))
+                        } else {
+                            String::new()
+                        };
+                        open.push_str(&format!("- [ ] {} ({}){}\n", item.title, list.name, so_far));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if done.is_empty() {
+            done.push_str("Nothing finished.\n");
+        }
+        if open.is_empty() {
+            open.push_str("Nothing left open.\n");
+        }
+        let mut note = format!(
+            "# {}\n\n## Done\n\n{}\n## Carried over\n\n{}",
+            day.format("%Y-%m-%d"),
+            done,
+            open
+        );
+        if !tags.is_empty() {
+            note.push_str(&format!("\n## Time by tag\n\n{}", tags));
+        }
+        note
+    }
+
+    /// Writes today's note to a file named after the date, replacing an
+    /// earlier one from the same day, and returns the file name.
+    pub fn write_daily_note(&self) -> Result<String, Box<dyn Error>> {
+        let today = Local::today().naive_local();
+        let path = format!("{}.md", today.format("%Y-%m-%d"));
+        fs::write(&path, self.daily_note(today))?;
+        Ok(path)
+    }
+
+    /// Time tracked today per list on one line, for pasting into a standup.
+    pub fn standup_summary(&self) -> String {
+        let lists = self
+            .group_list
+            .items
+            .iter()
+            .filter(|list| list.today_duration() > 0)
+            .map(|list| {
+                format!(
+                    "{}: {}",
+                    list.name,
+                    format_duration(list.today_duration(), self.config.duration_precision)
+                )
+            })
+            .collect::<Vec<_>>();
+        if lists.is_empty() {
+            "Nothing tracked today".to_string()
+        } else {
+            lists.join(" · ")
+        }
+    }
+
+    /// Writes a CSV with a row per item and a column per day from `from` to
+    /// `to`, each cell the hours the timers counted on the item that day,
+    /// empty for none. Time set by hand, like an edited duration, has no
+    /// day and is left out. A row per tag follows with the tag totals,
+    /// split between an item's tags as in `tag_durations`.
+    pub fn export_daily_breakdown_csv(
+        &self,
+        path: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<(), Box<dyn Error>> {
+        let days = std::iter::successors(Some(from), |day| day.succ_opt())
+            .take_while(|day| *day <= to)
+            .collect::<Vec<_>>();
+        let mut tags = BTreeMap::<&str, BTreeMap<NaiveDate, i64>>::new();
+        let mut csv = String::from("List,Item");
+        for day in &days {
+            csv.push_str(&format!(",{}", day.format("%Y-%m-%d")));
+        }
+        csv.push_str(",Total\n");
+        for list in &self.group_list.items {
+            for item in list.list.items.iter().filter(|item| item.is_task()) {
+                csv.push_str(&format!(
+                    "{},{}",
+                    csv_field(&list.name),
+                    csv_field(&item.title)
+                ));
+                push_day_cells(&mut csv, &days, &item.daily_log);
+                for tag in &item.tags {
+                    let times = tags.entry(tag).or_default();
+                    for (day, millis) in &item.daily_log {
+                        *times.entry(*day).or_insert(0) += self.tag_share(item, *millis);
+                    }
+                }
+            }
+        }
+        for (tag, times) in &tags {
+            csv.push_str(&format!("Tag,{}", csv_field(tag)));
+            push_day_cells(&mut csv, &days, times);
+        }
+        fs::write(path, csv)?;
+        Ok(())
+    }
+}
+
+/// Adds a cell per day with the hours logged that day and the total,
+/// ending the row.
+fn push_day_cells(csv: &mut String, days: &[NaiveDate], times: &BTreeMap<NaiveDate, i64>) {
+    let total = days.iter().filter_map(|day| times.get(day)).sum::<i64>();
+    for day in days {
+        match times.get(day) {
+            Some(millis) if *millis > 0 => csv.push_str(&format!(",{}", hours(*millis))),
+            _ => csv.push(','),
+        }
+    }
+    csv.push_str(&format!(",{}\n", hours(total)));
+}
+
+/// Hours with up to two decimals, for spreadsheets.
+fn hours(millis: i64) -> String {
+    format!(
+        "{:.2}",
+        millis as f64 / Duration::hours(1).num_milliseconds() as f64
+    )
+}
+
+/// Quotes a CSV field if it holds a separator, quote or line break.
+fn csv_field(text: &str) -> String {
+    if text.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Replaces characters that are awkward in file names.
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breakdown_has_a_row_per_item_and_a_column_per_day() {
+        let day = NaiveDate::from_ymd(2024, 3, 4);
+        let mut app = App::new("Test".to_string());
+        let mut list = GroupList::new("Work, client".to_string());
+        let mut logged = Item {
+            title: "Say \"hi\" {list}".to_string(),
+            ..Item::default()
+        };
+        logged.daily_log.insert(day, 90 * 60_000);
+        logged.daily_log.insert(day.succ(), 15 * 60_000);
+        // Outside of the range.
+        logged.daily_log.insert(day.pred(), 60 * 60_000);
+        logged.tags = vec!["client".to_string(), "calls".to_string()];
+        list.list.add(logged);
+        let mut other = Item {
+            title: "Idle".to_string(),
+            tags: vec!["client".to_string()],
+            ..Item::default()
+        };
+        other.daily_log.insert(day + Duration::days(2), 30 * 60_000);
+        list.list.add(other);
+        app.group_list.add(list);
+
+        let path = std::env::temp_dir().join("todo-timer-breakdown-test.csv");
+        let path = path.to_str().unwrap();
+        app.export_daily_breakdown_csv(path, day, day + Duration::days(2))
+            .unwrap();
+        let csv = fs::read_to_string(path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(
+            csv,
+            "List,Item,2024-03-04,2024-03-05,2024-03-06,Total\n\
+             \"Work, client\",\"Say \"\"hi\"\" {list}\",1.50,0.25,,1.75\n\
+             \"Work, client\",Idle,,,0.50,0.50\n\
+             Tag,calls,1.50,0.25,,1.75\n\
+             Tag,client,1.50,0.25,0.50,2.25\n"
+        );
+
+        app.config.split_tag_time = true;
+        app.export_daily_breakdown_csv(path, day, day).unwrap();
+        let csv = fs::read_to_string(path).unwrap();
+        fs::remove_file(path).unwrap();
+        assert!(csv.ends_with("Tag,calls,0.75,0.75\nTag,client,0.75,0.75\n"));
+    }
+}
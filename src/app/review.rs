@@ -0,0 +1,229 @@
+use crate::app::app::{format_duration, App, DUE_FORMAT};
+use crate::app::undo::Operation;
+
+use chrono::{Duration, Local};
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// The open items of all lists, stepped through one at a time.
+pub struct Review {
+    /// List index and id of every item to review.
+    queue: Vec<(usize, u64)>,
+    position: usize,
+    done: usize,
+    snoozed: usize,
+    deleted: usize,
+}
+
+impl App {
+    pub fn start_review(&mut self) {
+        let queue = self
+            .group_list
+            .items
+            .iter()
+            .enumerate()
+            .flat_map(|(list_index, list)| {
+                list.list
+                    .items
+                    .iter()
+                    .filter(|item| item.is_task() && !item.done() && !item.scheduled())
+                    .map(move |item| (list_index, item.id))
+            })
+            .collect::<Vec<_>>();
+        if queue.is_empty() {
+            self.notify("Nothing open to review".to_string());
+            return;
+        }
+        self.review = Some(Review {
+            queue,
+            position: 0,
+            done: 0,
+            snoozed: 0,
+            deleted: 0,
+        });
+    }
+
+    /// The item under review, skipping ones that went away meanwhile.
+    fn review_current(&mut self) -> Option<(usize, u64)> {
+        loop {
+            let review = self.review.as_ref()?;
+            let (list_index, id) = *review.queue.get(review.position)?;
+            if self.find_item(list_index, id).is_some() {
+                return Some((list_index, id));
+            }
+            self.review.as_mut()?.position += 1;
+        }
+    }
+
+    fn finish_review(&mut self) {
+        if let Some(review) = self.review.take() {
+            self.notify(format!(
+                "Reviewed {} items: {} done, {} snoozed, {} deleted",
+                review.position.min(review.queue.len()),
+                review.done,
+                review.snoozed,
+                review.deleted
+            ));
+        }
+    }
+
+    /// d marks done, s starts the item tomorrow and moves its due date a
+    /// day, x deletes, Space skips. Each moves on to the next item.
+    pub fn process_review(&mut self, key: KeyCode) {
+        let (list_index, id) = match self.review_current() {
+            Some(current) => current,
+            None => {
+                self.finish_review();
+                return;
+            }
+        };
+        let locked = self
+            .find_item(list_index, id)
+            .map(|item| item.locked)
+            .unwrap_or_default();
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.finish_review();
+                return;
+            }
+            KeyCode::Char('d') | KeyCode::Char('s') | KeyCode::Char('x') if locked => {
+                self.notify("The item is locked, skip it with Space".to_string());
+                return;
+            }
+            KeyCode::Char('d') => {
+                if let Some(item) = self.find_item_mut(list_index, id) {
+                    let previous = item.clone();
+                    item.end_at = Some(Local::now());
+                    let item = item.clone();
+                    self.record(Operation::Edit {
+                        list_index,
+                        item: previous,
+                    });
+                    let list_name = self.group_list.items[list_index].name.clone();
+                    self.run_done_hook(&list_name, &item);
+                }
+                if let Some(review) = &mut self.review {
+                    review.done += 1;
+                }
+            }
+            KeyCode::Char('s') => {
+                let tomorrow = Local::today().naive_local().succ();
+                if let Some(item) = self.find_item_mut(list_index, id) {
+                    let previous = item.clone();
+                    item.start_date = Some(tomorrow);
+                    item.due_at = item.due_at.map(|due_at| due_at + Duration::days(1));
+                    self.record(Operation::Edit {
+                        list_index,
+                        item: previous,
+                    });
+                }
+                if let Some(review) = &mut self.review {
+                    review.snoozed += 1;
+                }
+            }
+            KeyCode::Char('x') => {
+                let list = &mut self.group_list.items[list_index].list;
+                if let Some(index) = list.items.iter().position(|item| item.id == id) {
+                    let item = list.items.remove(index);
+                    list.clamp_selection();
+                    self.bin_item(list_index, index, item.clone());
+                    self.record(Operation::DeleteItem {
+                        list_index,
+                        index,
+                        item,
+                    });
+                }
+                if let Some(review) = &mut self.review {
+                    review.deleted += 1;
+                }
+            }
+            KeyCode::Char(' ') | KeyCode::Right => {}
+            _ => return,
+        }
+        if let Some(review) = &mut self.review {
+            review.position += 1;
+        }
+        if self.review_current().is_none() {
+            self.finish_review();
+        }
+    }
+
+    pub fn draw_review<B: Backend>(&self, frame: &mut Frame<B>) {
+        let review = match &self.review {
+            Some(review) => review,
+            None => return,
+        };
+        let (list_index, id) = match review.queue.get(review.position) {
+            Some(current) => *current,
+            None => return,
+        };
+        let item = match self.find_item(list_index, id) {
+            Some(item) => item,
+            None => return,
+        };
+        let size = frame.size();
+        let review_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let mut text = Text::from(vec![
+            Spans::from(vec![Span::styled(
+                format!(
+                    "{} of {} · {}",
+                    review.position + 1,
+                    review.queue.len(),
+                    self.group_list.items[list_index].name
+                ),
+                Style::default().fg(Color::Gray),
+            )]),
+            Spans::from(vec![Span::styled(
+                item.title.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )]),
+            Spans::from(vec![Span::raw(format!(
+                "Tracked: {}",
+                format_duration(item.duration, self.config.duration_precision)
+            ))]),
+        ]);
+        if let Some(due_at) = item.due_at {
+            text.lines.push(Spans::from(vec![Span::raw(format!(
+                "Due: {}",
+                due_at.format(DUE_FORMAT)
+            ))]));
+        }
+        if !item.tags.is_empty() {
+            text.lines.push(Spans::from(vec![Span::raw(format!(
+                "Tags: {}",
+                item.tags.join(", ")
+            ))]));
+        }
+        if !item.desc.is_empty() {
+            text.lines.push(Spans::from(vec![Span::raw("")]));
+            text.extend(Text::from(item.desc.clone()));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Review · d: done · s: snooze · x: delete · Space: skip · Esc: stop ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, review_size);
+        frame.render_widget(paragraph, review_size);
+    }
+}
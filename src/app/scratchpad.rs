@@ -0,0 +1,80 @@
+use crate::app::app::{pop_grapheme, App};
+
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::Alignment,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// How many lines the scratchpad is scrolled up from its end. Typing jumps
+/// back to the end, where the text is entered.
+pub struct ScratchpadView {
+    pub scroll_back: u16,
+}
+
+impl App {
+    pub fn process_scratchpad(&mut self, key: KeyCode) {
+        let view = match &mut self.scratchpad_view {
+            Some(view) => view,
+            None => return,
+        };
+        match key {
+            KeyCode::Esc => self.scratchpad_view = None,
+            KeyCode::Up => view.scroll_back = view.scroll_back.saturating_add(1),
+            KeyCode::Down => view.scroll_back = view.scroll_back.saturating_sub(1),
+            KeyCode::PageUp => view.scroll_back = view.scroll_back.saturating_add(10),
+            KeyCode::PageDown => view.scroll_back = view.scroll_back.saturating_sub(10),
+            KeyCode::Enter => {
+                view.scroll_back = 0;
+                self.scratchpad.push('\n');
+            }
+            KeyCode::Backspace => {
+                view.scroll_back = 0;
+                pop_grapheme(&mut self.scratchpad);
+            }
+            KeyCode::Char(x) => {
+                view.scroll_back = 0;
+                self.scratchpad.push(x);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw_scratchpad<B: Backend>(&self, frame: &mut Frame<B>) {
+        let view = match &self.scratchpad_view {
+            Some(view) => view,
+            None => return,
+        };
+        let size = frame.size();
+        let text = format!("{}_", self.scratchpad);
+
+        // Count the lines after wrapping to know how far the end is.
+        let width = size.width.saturating_sub(2).max(1) as usize;
+        let lines = text
+            .split('\n')
+            .map(|line| line.width().saturating_sub(1) / width + 1)
+            .sum::<usize>() as u16;
+        let height = size.height.saturating_sub(2);
+        let bottom = lines.saturating_sub(height);
+        let top = bottom.saturating_sub(view.scroll_back);
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Scratchpad · Up / Down: scroll · Esc: close ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Black)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: false })
+            .scroll((top, 0));
+
+        frame.render_widget(Clear, size);
+        frame.render_widget(paragraph, size);
+    }
+}
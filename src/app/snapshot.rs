@@ -0,0 +1,41 @@
+use crate::app::app::App;
+
+use crossterm::terminal;
+use std::error::Error;
+use std::fs;
+use tui::{backend::TestBackend, Terminal};
+
+impl App {
+    /// Writes the screen as it is drawn now to the snapshot file as plain
+    /// text, for sharing or bug reports.
+    pub fn write_snapshot(&mut self) {
+        let path = self.config.snapshot_file.clone();
+        let message = match self.render_text() {
+            Ok(text) => match fs::write(&path, text) {
+                Ok(()) => format!("Snapshot written to {}", path),
+                Err(err) => format!("Could not write {}: {}", path, err),
+            },
+            Err(err) => format!("Could not take snapshot: {}", err),
+        };
+        self.notify(message);
+    }
+
+    /// Draws the app into an off-screen buffer of the terminal's size and
+    /// returns its lines without trailing blanks.
+    fn render_text(&mut self) -> Result<String, Box<dyn Error>> {
+        let (width, height) = terminal::size()?;
+        let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+        terminal.draw(|frame| self.draw(frame))?;
+
+        let buffer = terminal.backend().buffer();
+        let mut text = String::new();
+        for y in 0..buffer.area.height {
+            let line = (0..buffer.area.width)
+                .map(|x| buffer.get(x, y).symbol.as_str())
+                .collect::<String>();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        Ok(text)
+    }
+}
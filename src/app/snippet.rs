@@ -0,0 +1,167 @@
+use crate::app::app::{App, GroupList, Item, Subtask};
+use crate::app::clipboard;
+use crate::app::stateful_list::StatefulList;
+
+use std::error::Error;
+use std::fs;
+
+/// The item's definition without ids, timer state or daily marks, for
+/// sharing it as a template.
+fn template(item: &Item) -> Item {
+    Item {
+        title: item.title.clone(),
+        desc: item.desc.clone(),
+        target: item.target,
+        auto_stop_at_target: item.auto_stop_at_target,
+        kind: item.kind,
+        mode: item.mode,
+        due_at: item.due_at,
+        tags: item.tags.clone(),
+        link: item.link.clone(),
+        points: item.points,
+        non_billable: item.non_billable,
+        project_code: item.project_code.clone(),
+        alarms: item.alarms.clone(),
+        subtasks: item
+            .subtasks
+            .iter()
+            .map(|subtask| Subtask {
+                title: subtask.title.clone(),
+                done: false,
+            })
+            .collect(),
+        meta: item.meta.clone(),
+        ..Item::default()
+    }
+}
+
+/// The list's settings and item templates, without selections or paused
+/// timers, for sharing it as a template.
+fn list_template(list: &GroupList<Item>) -> GroupList<Item> {
+    GroupList {
+        id: 0,
+        name: list.name.clone(),
+        sort_mode: list.sort_mode,
+        hourly_rate: list.hourly_rate,
+        currency: list.currency.clone(),
+        timebox_minutes: list.timebox_minutes,
+        wip_limit: list.wip_limit,
+        color: list.color.clone(),
+        icon: list.icon.clone(),
+        display_rounding: list.display_rounding,
+        project_code: list.project_code.clone(),
+        weekly_budget: list.weekly_budget,
+        collapsed: list.collapsed,
+        paused_ids: Vec::new(),
+        list: StatefulList {
+            items: list.list.items.iter().map(template).collect(),
+            ..StatefulList::new()
+        },
+    }
+}
+
+impl App {
+    /// Copies the selected list as a template to the clipboard, for pasting
+    /// into another board with Alt+i.
+    pub fn copy_list_snippet(&mut self) {
+        let list = match self
+            .group_list
+            .state
+            .selected()
+            .and_then(|index| self.group_list.items.get(index))
+        {
+            Some(list) => list_template(list),
+            None => return,
+        };
+        self.notify(
+            match toml::to_string(&list)
+                .map_err(|err| err.to_string())
+                .and_then(|text| clipboard::copy(&text).map_err(|err| err.to_string()))
+            {
+                Ok(()) => format!("Copied list \"{}\" to the clipboard", list.name),
+                Err(err) => format!("Could not copy list: {}", err),
+            },
+        );
+    }
+
+    /// Adds the list template from the clipboard after the other lists, with
+    /// fresh ids and no tracked time.
+    pub fn paste_list_snippet(&mut self) {
+        let list = match clipboard::paste()
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                toml::from_str::<GroupList<Item>>(&text).map_err(|err| err.to_string())
+            }) {
+            Ok(list) => list,
+            Err(err) => {
+                self.notify(format!("Clipboard holds no list: {}", err));
+                return;
+            }
+        };
+        if list.name.trim().is_empty() {
+            self.notify("The list in the clipboard has no name".to_string());
+            return;
+        }
+        let mut list = list_template(&list);
+        list.id = self.new_id();
+        for item in &mut list.list.items {
+            item.id = self.new_id();
+        }
+        self.notify(format!(
+            "Pasted list \"{}\" with {} items",
+            list.name,
+            list.list.items.len()
+        ));
+        self.group_list.add(list);
+    }
+
+    /// Writes the selected item as a template to the snippet file.
+    pub fn export_snippet(&mut self) {
+        let item = match self.selected_item() {
+            Some((list_index, index)) => {
+                template(&self.group_list.items[list_index].list.items[index])
+            }
+            None => return,
+        };
+        let path = self.config.snippet_file.clone();
+        self.notify(match write_snippet(&path, &item) {
+            Ok(()) => format!("Wrote \"{}\" to {}", item.title, path),
+            Err(err) => format!("Could not write {}: {}", path, err),
+        });
+    }
+
+    /// Adds the item from the snippet file to the open list.
+    pub fn import_snippet(&mut self) {
+        let list_index = match self.active_list {
+            Some(index) => index,
+            None => {
+                self.notify("Open a list to import into".to_string());
+                return;
+            }
+        };
+        let path = self.config.snippet_file.clone();
+        let mut item = match read_snippet(&path) {
+            Ok(item) => template(&item),
+            Err(err) => {
+                self.notify(format!("Could not read {}: {}", path, err));
+                return;
+            }
+        };
+        if item.title.trim().is_empty() {
+            self.notify(format!("{} has no title", path));
+            return;
+        }
+        item.id = self.new_id();
+        self.notify(format!("Imported \"{}\"", item.title));
+        self.group_list.items[list_index].list.add(item);
+    }
+}
+
+fn write_snippet(path: &str, item: &Item) -> Result<(), Box<dyn Error>> {
+    fs::write(path, toml::to_string(item)?)?;
+    Ok(())
+}
+
+fn read_snippet(path: &str) -> Result<Item, Box<dyn Error>> {
+    Ok(toml::from_str(&fs::read_to_string(path)?)?)
+}
@@ -0,0 +1,146 @@
+use crate::app::app::{App, GroupList, Item};
+
+use rusqlite::{params, Connection};
+use std::error::Error;
+use std::mem;
+
+/// One row per list and one per item, in display order. The columns hold the
+/// fields other tools most likely query; `data` keeps the whole struct as
+/// TOML, so fields added later round-trip without a schema change.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS app (
+        id INTEGER PRIMARY KEY CHECK (id = 0),
+        data TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS groups (
+        position INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        selected INTEGER,
+        data TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS items (
+        group_position INTEGER NOT NULL REFERENCES groups (position),
+        position INTEGER NOT NULL,
+        id INTEGER NOT NULL,
+        title TEXT NOT NULL,
+        desc TEXT NOT NULL,
+        start_at TEXT,
+        end_at TEXT,
+        duration INTEGER NOT NULL,
+        paused INTEGER NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (group_position, position)
+    );
+";
+
+/// Whether `path` names a SQLite database rather than a TOML file.
+pub fn is_sqlite(path: &str) -> bool {
+    path.ends_with(".sqlite") || path.ends_with(".db")
+}
+
+impl App {
+    /// Reads the app from the SQLite database at `path`, starting a fresh one
+    /// if it holds no app yet.
+    pub fn load_sqlite(path: &str) -> Result<App, Box<dyn Error>> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+
+        let mut stmt = conn.prepare("SELECT data FROM app WHERE id = 0")?;
+        let data = stmt
+            .query_map(params![], |row| row.get::<_, String>(0))?
+            .next()
+            .transpose()?;
+        let mut app: App = match data {
+            Some(data) => toml::from_str(&data)?,
+            None => return Ok(App::loaded(App::new("Todo-Timer".to_string()), path)),
+        };
+
+        let mut stmt = conn.prepare("SELECT position, data FROM groups ORDER BY position")?;
+        let groups = stmt
+            .query_map(params![], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut stmt =
+            conn.prepare("SELECT data FROM items WHERE group_position = ?1 ORDER BY position")?;
+        let mut lists = Vec::new();
+        for (position, data) in groups {
+            let mut list: GroupList<Item> = toml::from_str(&data)?;
+            for data in stmt
+                .query_map(params![position], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?
+            {
+                list.list.items.push(toml::from_str(&data)?);
+            }
+            lists.push(list);
+        }
+        app.group_list.items = lists;
+
+        Ok(App::loaded(app, path))
+    }
+
+    /// Replaces the contents of the SQLite database at `path` with the app.
+    pub fn save_sqlite(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let mut conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+
+        // The nested lists get their own rows, so serialize the app and each
+        // list without them.
+        let mut lists = mem::take(&mut self.group_list.items);
+        let data = toml::to_string(self);
+        let mut items = Vec::new();
+        let mut groups = Vec::new();
+        for list in &mut lists {
+            items.push(mem::take(&mut list.list.items));
+            groups.push(toml::to_string(list));
+        }
+        for (list, list_items) in lists.iter_mut().zip(items.iter_mut()) {
+            list.list.items = mem::take(list_items);
+        }
+        self.group_list.items = lists;
+        let data = data?;
+
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM items", params![])?;
+        tx.execute("DELETE FROM groups", params![])?;
+        tx.execute(
+            "INSERT OR REPLACE INTO app (id, data) VALUES (0, ?1)",
+            params![data],
+        )?;
+        for (position, (list, group)) in self.group_list.items.iter().zip(groups).enumerate() {
+            tx.execute(
+                "INSERT INTO groups (position, name, selected, data) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    position as i64,
+                    list.name,
+                    list.list.state.selected().map(|index| index as i64),
+                    group?
+                ],
+            )?;
+            for (index, item) in list.list.items.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO items (group_position, position, id, title, desc, start_at, \
+                     end_at, duration, paused, data) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                    params![
+                        position as i64,
+                        index as i64,
+                        item.id as i64,
+                        item.title,
+                        item.desc,
+                        item.start_at.map(|at| at.to_rfc3339()),
+                        item.end_at.map(|at| at.to_rfc3339()),
+                        item.duration,
+                        item.paused,
+                        toml::to_string(item)?
+                    ],
+                )?;
+            }
+        }
+        tx.commit()?;
+
+        self.mark_saved();
+        Ok(())
+    }
+}
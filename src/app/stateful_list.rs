@@ -1,4 +1,4 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tui::widgets::ListState;
 
 pub enum Direction {
@@ -6,9 +6,24 @@ pub enum Direction {
     Down,
 }
 
+/// Persists only the selected index of a `ListState`.
+mod selection {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(state: &ListState, serializer: S) -> Result<S::Ok, S::Error> {
+        state.selected().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ListState, D::Error> {
+        let mut state = ListState::default();
+        state.select(Option::<usize>::deserialize(deserializer)?);
+        Ok(state)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct StatefulList<T> {
-    #[serde(skip)]
+    #[serde(default, rename = "selected", with = "selection")]
     pub state: ListState,
     pub items: Vec<T>,
 }
@@ -21,7 +36,9 @@ impl<T> StatefulList<T> {
         }
     }
 
-    pub fn move_selected_item(&mut self, direction: Direction) {
+    /// Swaps the selected item with its neighbour and returns the swapped
+    /// indices as `(from, to)`.
+    pub fn move_selected_item(&mut self, direction: Direction) -> Option<(usize, usize)> {
         if let Some(index) = self.state.selected() {
             match direction {
                 Direction::Down => {
@@ -32,6 +49,7 @@ impl<T> StatefulList<T> {
                     };
                     self.items.swap(index, target);
                     self.previous();
+                    Some((index, target))
                 }
                 Direction::Up => {
                     let target = if index == self.items.len() - 1 {
@@ -41,48 +59,207 @@ impl<T> StatefulList<T> {
                     };
                     self.items.swap(index, target);
                     self.next();
+                    Some((index, target))
                 }
             }
+        } else {
+            None
         }
     }
 
+    /// Moves the selected item to `target`, keeping it selected, and returns
+    /// the indices as `(from, to)`.
+    pub fn move_selected_to(&mut self, target: usize) -> Option<(usize, usize)> {
+        let index = self
+            .state
+            .selected()
+            .filter(|index| *index < self.items.len())?;
+        let item = self.items.remove(index);
+        let target = target.min(self.items.len());
+        self.items.insert(target, item);
+        self.state.select(Some(target));
+        Some((index, target))
+    }
+
+    pub fn move_to_top(&mut self) -> Option<(usize, usize)> {
+        self.move_selected_to(0)
+    }
+
+    pub fn move_to_bottom(&mut self) -> Option<(usize, usize)> {
+        let last = self.items.len().checked_sub(1)?;
+        self.move_selected_to(last)
+    }
+
     pub fn next(&mut self) {
-        if self.items.is_empty() {
-            return;
-        }
+        self.next_matching(true, |_| true);
+    }
 
-        let i = match self.state.selected() {
-            Some(i) => {
-                if i >= self.items.len() - 1 {
-                    0
-                } else {
-                    i + 1
-                }
-            }
+    pub fn previous(&mut self) {
+        self.previous_matching(true, |_| true);
+    }
+
+    /// Selects the next item that `matches`, stopping at the last item unless
+    /// `wrap` is set.
+    pub fn next_matching<F: Fn(&T) -> bool>(&mut self, wrap: bool, matches: F) {
+        let start = match self.state.selected() {
+            Some(i) => i as isize + 1,
             None => 0,
         };
-        self.state.select(Some(i));
+        self.select_matching(start, 1, wrap, matches);
     }
 
-    pub fn previous(&mut self) {
-        if self.items.is_empty() {
-            return;
-        }
+    /// Selects the previous item that `matches`, stopping at the first item
+    /// unless `wrap` is set.
+    pub fn previous_matching<F: Fn(&T) -> bool>(&mut self, wrap: bool, matches: F) {
+        let start = match self.state.selected() {
+            Some(i) => i as isize - 1,
+            None => 0,
+        };
+        self.select_matching(start, -1, wrap, matches);
+    }
 
-        let i = match self.state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    self.items.len() - 1
+    fn select_matching<F: Fn(&T) -> bool>(
+        &mut self,
+        start: isize,
+        step: isize,
+        wrap: bool,
+        matches: F,
+    ) {
+        let len = self.items.len() as isize;
+        let found = (0..len)
+            .map(|offset| start + offset * step)
+            .filter_map(|i| {
+                if wrap {
+                    Some(i.rem_euclid(len))
+                } else if i >= 0 && i < len {
+                    Some(i)
                 } else {
-                    i - 1
+                    None
                 }
+            })
+            .find(|i| matches(&self.items[*i as usize]));
+        if let Some(i) = found {
+            self.state.select(Some(i as usize));
+        }
+    }
+
+    /// Drops a selection that points past the end of the list.
+    pub fn clamp_selection(&mut self) {
+        if let Some(index) = self.state.selected() {
+            if self.items.is_empty() {
+                self.state.select(None);
+            } else if index >= self.items.len() {
+                self.state.select(Some(self.items.len() - 1));
             }
-            None => 0,
-        };
-        self.state.select(Some(i));
+        }
+    }
+
+    /// Forgets the scroll position, so the next render scrolls just far
+    /// enough to show the selected item.
+    pub fn reset_offset(&mut self) {
+        let selected = self.state.selected();
+        self.state.select(None);
+        self.state.select(selected);
     }
 
     pub fn add(&mut self, item: T) {
         self.items.push(item);
     }
+
+    pub fn remove_selected(&mut self) -> Option<T> {
+        let index = self
+            .state
+            .selected()
+            .filter(|index| *index < self.items.len())?;
+        let item = self.items.remove(index);
+        if self.items.is_empty() {
+            self.state.select(None);
+        } else if index >= self.items.len() {
+            self.state.select(Some(self.items.len() - 1));
+        }
+        Some(item)
+    }
+
+    pub fn insert_at_selected(&mut self, item: T) {
+        let index = self
+            .state
+            .selected()
+            .map_or(self.items.len(), |index| index.min(self.items.len()));
+        self.items.insert(index, item);
+        self.state.select(Some(index));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(items: &[u32], selected: Option<usize>) -> StatefulList<u32> {
+        let mut list = StatefulList::new();
+        list.items = items.to_vec();
+        list.state.select(selected);
+        list
+    }
+
+    #[test]
+    fn move_to_top_and_bottom_follow_the_item() {
+        let mut list = numbers(&[1, 2, 3], Some(1));
+        assert_eq!(list.move_to_top(), Some((1, 0)));
+        assert_eq!(list.items, vec![2, 1, 3]);
+        assert_eq!(list.state.selected(), Some(0));
+        assert_eq!(list.move_to_bottom(), Some((0, 2)));
+        assert_eq!(list.items, vec![1, 3, 2]);
+        assert_eq!(list.state.selected(), Some(2));
+    }
+
+    #[test]
+    fn moving_a_single_item_keeps_it_in_place() {
+        let mut list = numbers(&[1], Some(0));
+        assert_eq!(list.move_to_top(), Some((0, 0)));
+        assert_eq!(list.move_to_bottom(), Some((0, 0)));
+        assert_eq!(list.items, vec![1]);
+        assert_eq!(list.state.selected(), Some(0));
+    }
+
+    #[test]
+    fn moving_in_an_empty_list_does_nothing() {
+        let mut list = numbers(&[], None);
+        assert_eq!(list.move_to_top(), None);
+        assert_eq!(list.move_to_bottom(), None);
+
+        // A selection left over from removed items is no item either.
+        let mut list = numbers(&[], Some(0));
+        assert_eq!(list.move_to_top(), None);
+        assert_eq!(list.move_to_bottom(), None);
+    }
+
+    #[test]
+    fn wrapping_navigation_jumps_around_the_ends() {
+        let mut list = numbers(&[1, 2, 3], Some(2));
+        list.next_matching(true, |_| true);
+        assert_eq!(list.state.selected(), Some(0));
+        list.previous_matching(true, |_| true);
+        assert_eq!(list.state.selected(), Some(2));
+    }
+
+    #[test]
+    fn navigation_without_wrap_stops_at_the_ends() {
+        let mut list = numbers(&[1, 2, 3], Some(2));
+        list.next_matching(false, |_| true);
+        assert_eq!(list.state.selected(), Some(2));
+        list.state.select(Some(0));
+        list.previous_matching(false, |_| true);
+        assert_eq!(list.state.selected(), Some(0));
+    }
+
+    #[test]
+    fn navigation_skips_items_that_do_not_match() {
+        let mut list = numbers(&[1, 2, 3, 4], Some(0));
+        list.next_matching(false, |n| n % 2 == 1);
+        assert_eq!(list.state.selected(), Some(2));
+        list.next_matching(false, |n| n % 2 == 1);
+        assert_eq!(list.state.selected(), Some(2));
+        list.next_matching(true, |n| n % 2 == 1);
+        assert_eq!(list.state.selected(), Some(0));
+    }
 }
@@ -0,0 +1,97 @@
+use crate::app::app::App;
+
+use crossterm::event::KeyCode;
+use std::time::{Duration, Instant};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Messages kept for the history.
+const STATUS_LIMIT: usize = 50;
+/// How long the latest message stays in the status bar.
+const STATUS_SECONDS: u64 = 5;
+
+impl App {
+    /// Shows `message` in the status bar for a few seconds and keeps it in
+    /// the history.
+    pub fn notify(&mut self, message: impl Into<String>) {
+        if self.status.len() >= STATUS_LIMIT {
+            self.status.pop_front();
+        }
+        self.status.push_back((Instant::now(), message.into()));
+    }
+
+    /// The latest message while it is still fresh.
+    pub fn current_status(&self) -> Option<&str> {
+        self.status
+            .back()
+            .filter(|(at, _)| at.elapsed() < Duration::from_secs(STATUS_SECONDS))
+            .map(|(_, message)| message.as_str())
+    }
+
+    pub fn process_status_history(&mut self, key: KeyCode) {
+        if let KeyCode::Esc | KeyCode::Char('g') = key {
+            self.show_status_history = false;
+        }
+    }
+
+    pub fn draw_status_history<B: Backend>(&self, frame: &mut Frame<B>) {
+        if !self.show_status_history {
+            return;
+        }
+        let size = frame.size();
+        let history_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let mut text = Text::default();
+        if self.status.is_empty() {
+            text.lines
+                .push(Spans::from(vec![Span::raw("No messages yet")]));
+        }
+        // Newest first, so the latest fit even when the panel is short.
+        for (at, message) in self.status.iter().rev() {
+            text.lines.push(Spans::from(vec![
+                Span::styled(
+                    format!("{:>6}  ", ago(at.elapsed())),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::raw(message.clone()),
+            ]));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Messages · Esc: close ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, history_size);
+        frame.render_widget(paragraph, history_size);
+    }
+}
+
+/// Rough age like "12s", "3m" or "2h".
+fn ago(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    if seconds < 60 {
+        format!("{}s", seconds)
+    } else if seconds < 3600 {
+        format!("{}m", seconds / 60)
+    } else {
+        format!("{}h", seconds / 3600)
+    }
+}
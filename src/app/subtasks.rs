@@ -0,0 +1,78 @@
+use crate::app::app::{App, Item};
+
+use crossterm::event::KeyCode;
+use tui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{List, ListItem},
+};
+
+impl App {
+    /// Moves the focus from the item list into the selected item's subtasks.
+    pub fn focus_subtasks(&mut self) -> bool {
+        let has_subtasks = self
+            .get_selected_item()
+            .map(|item| item.is_task() && !item.subtasks.is_empty())
+            .unwrap_or_default();
+        if has_subtasks {
+            self.subtask_focus = Some(0);
+        }
+        has_subtasks
+    }
+
+    /// Keys while the subtasks have the focus: Up / Down select, Space or
+    /// Enter check, Left or Esc give the focus back to the item list.
+    pub fn process_subtasks(&mut self, key: KeyCode) {
+        let auto_done = self.config.auto_done_on_subtasks;
+        let row = match self.subtask_focus {
+            Some(row) => row,
+            None => return,
+        };
+        let len = match self.get_selected_item() {
+            Some(item) if !item.subtasks.is_empty() => item.subtasks.len(),
+            _ => {
+                self.subtask_focus = None;
+                return;
+            }
+        };
+        match key {
+            KeyCode::Esc | KeyCode::Left => self.subtask_focus = None,
+            KeyCode::Up => self.subtask_focus = Some(row.saturating_sub(1)),
+            KeyCode::Down => self.subtask_focus = Some((row + 1).min(len - 1)),
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                if let Some(item) = self.get_selected_unlocked() {
+                    if let Some(subtask) = item.subtasks.get_mut(row) {
+                        subtask.done = !subtask.done;
+                    }
+                    if auto_done {
+                        item.sync_done_with_subtasks();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The item's subtasks as a checklist, highlighted while focused.
+    pub fn subtask_list(&self, item: &Item) -> List<'static> {
+        let rows = item
+            .subtasks
+            .iter()
+            .map(|subtask| {
+                let (check, style) = if subtask.done {
+                    ("[x]", Style::default().fg(Color::Green))
+                } else {
+                    ("[ ]", Style::default().fg(Color::White))
+                };
+                ListItem::new(Span::styled(format!("{} {}", check, subtask.title), style))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(rows).style(Style::default().bg(Color::Black));
+        if self.subtask_focus.is_some() {
+            list.highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                .highlight_symbol("> ")
+        } else {
+            list
+        }
+    }
+}
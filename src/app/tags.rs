@@ -0,0 +1,187 @@
+use crate::app::app::{format_duration, pop_grapheme, tag_color, App, Item};
+
+use crossterm::event::KeyCode;
+use std::collections::BTreeMap;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// The tag index with its selected row and the new name while renaming.
+pub struct TagView {
+    pub row: usize,
+    pub rename: Option<String>,
+}
+
+impl App {
+    /// All tags in use with the number of items carrying them.
+    fn tag_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for item in self
+            .group_list
+            .items
+            .iter()
+            .flat_map(|list| list.list.items.iter())
+        {
+            for tag in &item.tags {
+                *counts.entry(tag.clone()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Tracked time per tag, with each item's time counted in full for
+    /// every tag or split between them, as configured.
+    pub fn tag_durations(&self) -> BTreeMap<String, i64> {
+        let mut durations = BTreeMap::new();
+        for item in self
+            .group_list
+            .items
+            .iter()
+            .flat_map(|list| list.list.items.iter())
+        {
+            let share = self.tag_share(item, item.duration);
+            for tag in &item.tags {
+                *durations.entry(tag.clone()).or_insert(0) += share;
+            }
+        }
+        durations
+    }
+
+    /// The part of `millis` tracked on `item` that each of its tags gets.
+    pub fn tag_share(&self, item: &Item, millis: i64) -> i64 {
+        if self.config.split_tag_time && !item.tags.is_empty() {
+            millis / item.tags.len() as i64
+        } else {
+            millis
+        }
+    }
+
+    /// Renames `old` on every item. Items that already carry `new` keep it
+    /// only once.
+    pub fn rename_tag(&mut self, old: &str, new: &str) -> usize {
+        let mut count = 0;
+        for item in self
+            .group_list
+            .items
+            .iter_mut()
+            .flat_map(|list| list.list.items.iter_mut())
+        {
+            if !item.tags.iter().any(|tag| tag == old) {
+                continue;
+            }
+            let mut tags = Vec::new();
+            for tag in item.tags.drain(..) {
+                let tag = if tag == old { new.to_string() } else { tag };
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+            item.tags = tags;
+            count += 1;
+        }
+        count
+    }
+
+    pub fn process_tags(&mut self, key: KeyCode) {
+        let tags = self.tag_counts().into_keys().collect::<Vec<_>>();
+        let view = match &mut self.tag_view {
+            Some(view) => view,
+            None => return,
+        };
+        if let Some(name) = &mut view.rename {
+            match key {
+                KeyCode::Esc => view.rename = None,
+                KeyCode::Backspace => pop_grapheme(name),
+                KeyCode::Char(x) => name.push(x),
+                KeyCode::Enter => {
+                    let new = name.trim().to_string();
+                    view.rename = None;
+                    if let (Some(old), false) = (tags.get(view.row), new.is_empty()) {
+                        let count = self.rename_tag(old, &new);
+                        self.notify(format!(
+                            "Renamed \"{}\" to \"{}\" on {} items",
+                            old, new, count
+                        ));
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+        match key {
+            KeyCode::Esc | KeyCode::Char('g') => self.tag_view = None,
+            KeyCode::Up => view.row = view.row.saturating_sub(1),
+            KeyCode::Down if view.row + 1 < tags.len() => {
+                view.row += 1;
+            }
+            KeyCode::Enter => view.rename = tags.get(view.row).cloned(),
+            _ => {}
+        }
+    }
+
+    pub fn draw_tags<B: Backend>(&self, frame: &mut Frame<B>) {
+        let view = match &self.tag_view {
+            Some(view) => view,
+            None => return,
+        };
+        let size = frame.size();
+        let tags_size = Rect::new(
+            size.x + size.width / 6,
+            size.y + size.height / 6,
+            size.width * 2 / 3,
+            size.height * 2 / 3,
+        );
+
+        let counts = self.tag_counts();
+        let durations = self.tag_durations();
+        let mut text = Text::default();
+        if counts.is_empty() {
+            text.lines.push(Spans::from(vec![Span::raw("No tags yet")]));
+        }
+        for (row, (tag, count)) in counts.iter().enumerate() {
+            let selected = view.row == row;
+            let label = match &view.rename {
+                Some(name) if selected => format!("{}_", name),
+                _ => tag.clone(),
+            };
+            let style = if selected {
+                Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            text.lines.push(Spans::from(vec![
+                Span::styled(
+                    format!(" {} ", label),
+                    style.fg(Color::Black).bg(tag_color(tag)),
+                ),
+                Span::raw(format!(
+                    "  {} items · {}",
+                    count,
+                    format_duration(
+                        durations.get(tag).copied().unwrap_or_default(),
+                        self.config.duration_precision
+                    )
+                )),
+            ]));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(" Tags · Enter: rename · Esc: close ")
+                    .borders(Borders::ALL)
+                    .style(Style::default().bg(Color::Blue)),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Blue))
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, tags_size);
+        frame.render_widget(paragraph, tags_size);
+    }
+}
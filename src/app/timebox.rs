@@ -0,0 +1,94 @@
+use crate::app::app::{App, Item};
+
+use chrono::{Duration, Local};
+
+const DEFAULT_MINUTES: i64 = 10;
+
+/// The item running in a timeboxed list and the time left on its slice.
+pub struct Timebox {
+    pub list_index: usize,
+    pub id: u64,
+    pub remaining: i64,
+}
+
+fn open(item: &Item) -> bool {
+    item.is_task() && !item.done() && !item.locked
+}
+
+impl App {
+    /// Runs the active list as a timebox, starting with the selected item or
+    /// the first open one.
+    pub fn start_timebox(&mut self) {
+        let list_index = match self.active_list {
+            Some(list_index) => list_index,
+            None => return,
+        };
+        let list = &self.group_list.items[list_index].list;
+        let start = list
+            .state
+            .selected()
+            .filter(|index| open(&list.items[*index]))
+            .or_else(|| list.items.iter().position(open));
+        match start {
+            Some(index) => self.timebox_item(list_index, index),
+            None => self.notify("No open items to timebox".to_string()),
+        }
+    }
+
+    fn timebox_item(&mut self, list_index: usize, index: usize) {
+        let group_list = &mut self.group_list.items[list_index];
+        let slice = Duration::minutes(group_list.timebox_minutes.unwrap_or(DEFAULT_MINUTES));
+        let item = &mut group_list.list.items[index];
+        item.start();
+        let id = item.id;
+        group_list.list.state.select(Some(index));
+        self.timebox = Some(Timebox {
+            list_index,
+            id,
+            remaining: slice.num_milliseconds(),
+        });
+    }
+
+    /// Counts the slice down and moves on to the next open item once it is used up.
+    pub fn tick_timebox(&mut self, millis: i64) {
+        let (list_index, id) = match &mut self.timebox {
+            Some(timebox) => {
+                timebox.remaining -= millis;
+                if timebox.remaining > 0 {
+                    return;
+                }
+                (timebox.list_index, timebox.id)
+            }
+            None => return,
+        };
+
+        let next = self.group_list.items.get_mut(list_index).and_then(|list| {
+            let items = &mut list.list.items;
+            let index = items.iter().position(|item| item.id == id)?;
+            if items[index].end_at.is_none() {
+                items[index].end_at = Some(Local::now());
+            }
+            (index + 1..items.len()).find(|next| open(&items[*next]))
+        });
+        match next {
+            Some(index) => self.timebox_item(list_index, index),
+            None => {
+                self.timebox = None;
+                self.notify("Timebox finished".to_string());
+            }
+        }
+    }
+
+    pub fn stop_timebox(&mut self) {
+        self.timebox = None;
+        self.notify("Timebox stopped".to_string());
+    }
+
+    /// Time left on the slice of the item with `id`, if it is timeboxed.
+    pub fn timebox_remaining(&self, id: u64) -> Option<i64> {
+        self.timebox
+            .as_ref()
+            .filter(|timebox| timebox.id == id)
+            .map(|timebox| timebox.remaining.max(0))
+    }
+}
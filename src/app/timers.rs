@@ -0,0 +1,112 @@
+use crate::app::app::{format_duration, App, DialogState, Item, SubTimer};
+
+use crossterm::event::KeyCode;
+use tui::{
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+};
+
+impl App {
+    /// Moves the focus to the selected item's timers, or asks for the name
+    /// of its first one.
+    pub fn focus_timers(&mut self) {
+        let has_timers = match self.get_selected_unlocked() {
+            Some(item) if item.is_task() => !item.timers.is_empty(),
+            _ => return,
+        };
+        if has_timers {
+            self.timer_focus = Some(0);
+        } else {
+            self.dialog.display(DialogState::Timer);
+        }
+    }
+
+    /// Keys while the timers have the focus: Up / Down select, Space or
+    /// Enter start and stop, n adds and x removes a timer, Left or Esc give
+    /// the focus back to the item list.
+    pub fn process_timers(&mut self, key: KeyCode) {
+        let row = match self.timer_focus {
+            Some(row) => row,
+            None => return,
+        };
+        let len = match self.get_selected_unlocked() {
+            Some(item) if !item.timers.is_empty() => item.timers.len(),
+            _ => {
+                self.timer_focus = None;
+                return;
+            }
+        };
+        match key {
+            KeyCode::Esc | KeyCode::Left => self.timer_focus = None,
+            KeyCode::Up => self.timer_focus = Some(row.saturating_sub(1)),
+            KeyCode::Down => self.timer_focus = Some((row + 1).min(len - 1)),
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                if let Some(timer) = self
+                    .get_selected_unlocked()
+                    .filter(|item| !item.done())
+                    .and_then(|item| item.timers.get_mut(row))
+                {
+                    timer.running = !timer.running;
+                }
+            }
+            KeyCode::Char('n') => {
+                self.timer_focus = None;
+                self.dialog.display(DialogState::Timer);
+            }
+            // The removed timer's time stays in the item's total.
+            KeyCode::Char('x') => {
+                if let Some(item) = self.get_selected_unlocked() {
+                    item.timers.remove(row);
+                    if item.timers.is_empty() {
+                        self.timer_focus = None;
+                    } else {
+                        self.timer_focus = Some(row.min(len - 2));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Adds a stopped timer to the selected item and focuses it.
+    pub fn add_timer(&mut self, name: String) {
+        if let Some(item) = self.get_selected_unlocked() {
+            item.timers.push(SubTimer {
+                name,
+                duration: 0,
+                running: false,
+            });
+            self.timer_focus = Some(item.timers.len() - 1);
+        }
+    }
+
+    /// The item's timers for the card, the selected one highlighted while
+    /// focused.
+    pub fn timer_lines(&self, item: &Item) -> Vec<Spans<'static>> {
+        let mut lines = Vec::new();
+        if item.timers.is_empty() {
+            return lines;
+        }
+        lines.push(Spans::from(vec![Span::raw("Timers (Ctrl+k):")]));
+        for (row, timer) in item.timers.iter().enumerate() {
+            let mut style = if timer.running {
+                Style::default().fg(Color::LightGreen)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            if self.timer_focus == Some(row) {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            lines.push(Spans::from(vec![Span::styled(
+                format!(
+                    "{} {}  {}",
+                    if timer.running { "▶" } else { "■" },
+                    timer.name,
+                    format_duration(timer.duration, self.config.duration_precision)
+                ),
+                style,
+            )]));
+        }
+        lines
+    }
+}
@@ -0,0 +1,923 @@
+use crate::app::app::{App, GroupList, Item};
+use crate::app::stateful_list::StatefulList;
+
+use serde::{Deserialize, Serialize};
+
+const UNDO_LIMIT: usize = 100;
+/// Most recent changes kept in the database with `persist_undo`.
+const HISTORY_LIMIT: usize = 20;
+
+/// A change that can be reverted. Applying an operation returns the one
+/// that reverts it again, which is what moves between undo and redo.
+// TOML has no struct variants, so the variant goes into a `kind` field.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind")]
+pub enum Operation {
+    DeleteGroup {
+        index: usize,
+        list: GroupList<Item>,
+    },
+    /// A list that was added or put back, reverted by deleting it again.
+    /// Lists are found by id, since other changes may have moved it.
+    InsertGroup {
+        #[serde(default)]
+        id: u64,
+    },
+    /// Holds the name the list with `id` had before it was renamed.
+    Rename {
+        id: u64,
+        name: String,
+    },
+    DeleteItem {
+        list_index: usize,
+        index: usize,
+        item: Item,
+    },
+    /// An item that was added or put back, found by id when reverted.
+    InsertItem {
+        #[serde(default)]
+        id: u64,
+    },
+    /// Holds the item as it was before the edit was committed.
+    Edit {
+        list_index: usize,
+        item: Item,
+    },
+    Move {
+        list_index: Option<usize>,
+        from: usize,
+        to: usize,
+    },
+    /// An item taken out at `from` and inserted again at `to`.
+    Reinsert {
+        list_index: Option<usize>,
+        from: usize,
+        to: usize,
+    },
+    /// Two items that traded places, possibly across lists.
+    Swap {
+        first_list: usize,
+        first: usize,
+        second_list: usize,
+        second: usize,
+    },
+    /// The list taken out at `source` after its last `count` items went to
+    /// the end of `target`, kept without them. `target` is where that list
+    /// was before the merge.
+    // `count` comes first, TOML cannot have plain values after the list table.
+    Merge {
+        source: usize,
+        target: usize,
+        count: usize,
+        list: GroupList<Item>,
+    },
+    /// A merge that was undone, reverted by merging again.
+    Unmerge {
+        source: usize,
+        target: usize,
+    },
+    /// The item at `index` that became the list after `list_index`.
+    Promote {
+        list_index: usize,
+        index: usize,
+        item: Item,
+    },
+    /// A promotion that was undone, reverted by promoting again.
+    Unpromote {
+        list_index: usize,
+        index: usize,
+    },
+    /// Changes made together, undone together in reverse order.
+    Batch {
+        operations: Vec<Operation>,
+    },
+    /// An item moved from `from` in one list to `to` in another.
+    Transfer {
+        from_list: usize,
+        from: usize,
+        to_list: usize,
+        to: usize,
+    },
+}
+
+fn push_capped(stack: &mut Vec<Operation>, operation: Operation) {
+    if stack.len() >= UNDO_LIMIT {
+        stack.remove(0);
+    }
+    stack.push(operation);
+}
+
+impl App {
+    /// Copies the latest changes into the saved history, or clears it
+    /// unless `persist_undo` is set.
+    pub fn store_history(&mut self) {
+        self.history = if self.config.persist_undo {
+            let skip = self.undo.len().saturating_sub(HISTORY_LIMIT);
+            self.undo[skip..].to_vec()
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// Makes the history saved with the database undoable again.
+    pub fn restore_history(&mut self) {
+        self.undo = std::mem::take(&mut self.history);
+    }
+
+    /// Remembers a new change, which makes the undone ones unreachable.
+    pub fn record(&mut self, operation: Operation) {
+        push_capped(&mut self.undo, operation);
+        self.redo.clear();
+    }
+
+    /// Drops the latest undo and redo steps matching `deletion`, for
+    /// entries that came back through the bin instead.
+    pub fn forget_deletion(&mut self, deletion: impl Fn(&Operation) -> bool) {
+        for stack in [&mut self.undo, &mut self.redo].iter_mut() {
+            if let Some(pos) = stack.iter().rposition(&deletion) {
+                stack.remove(pos);
+            }
+        }
+    }
+
+    /// Puts back a pending cut first, since it is the latest change.
+    pub fn undo(&mut self) {
+        if self.put_back_cut() {
+            return;
+        }
+        if let Some(operation) = self.undo.pop() {
+            match self.revert(operation.clone()) {
+                Some(inverse) => push_capped(&mut self.redo, inverse),
+                None => {
+                    self.undo.push(operation);
+                    self.notify("Could not undo the last change".to_string());
+                }
+            }
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(operation) = self.redo.pop() {
+            match self.revert(operation.clone()) {
+                Some(inverse) => push_capped(&mut self.undo, inverse),
+                None => {
+                    self.redo.push(operation);
+                    self.notify("Could not redo the last change".to_string());
+                }
+            }
+        }
+    }
+
+    fn revert(&mut self, operation: Operation) -> Option<Operation> {
+        match operation {
+            Operation::DeleteGroup { index, list } => {
+                self.unbin_group(list.id);
+                let index = index.min(self.group_list.items.len());
+                self.group_list.items.insert(index, list);
+                if self.active_list.is_none() {
+                    self.group_list.state.select(Some(index));
+                }
+                Some(Operation::InsertGroup {
+                    id: self.group_list.items[index].id,
+                })
+            }
+            Operation::InsertGroup { id } => {
+                let index = self
+                    .group_list
+                    .items
+                    .iter()
+                    .position(|list| list.id == id)?;
+                let list = self.group_list.items.remove(index);
+                self.active_list = match self.active_list {
+                    Some(active) if active == index => None,
+                    Some(active) if active > index => Some(active - 1),
+                    active => active,
+                };
+                self.clamp_selection();
+                self.bin_group(index, list.clone());
+                Some(Operation::DeleteGroup { index, list })
+            }
+            Operation::Rename { id, name } => {
+                let list = self
+                    .group_list
+                    .items
+                    .iter_mut()
+                    .find(|list| list.id == id)?;
+                let name = std::mem::replace(&mut list.name, name);
+                Some(Operation::Rename { id, name })
+            }
+            Operation::DeleteItem {
+                list_index,
+                index,
+                item,
+            } => {
+                let id = item.id;
+                self.unbin_item(id);
+                let group_list = self.group_list.items.get_mut(list_index)?;
+                let list = &mut group_list.list;
+                let index = index.min(list.items.len());
+                list.items.insert(index, item);
+                if self.active_list == Some(list_index) {
+                    list.state.select(Some(index));
+                }
+                Some(Operation::InsertItem { id })
+            }
+            Operation::InsertItem { id } => {
+                let (list_index, index) = self.item_position(id)?;
+                let list = &mut self.group_list.items[list_index].list;
+                let item = list.items.remove(index);
+                list.clamp_selection();
+                self.bin_item(list_index, index, item.clone());
+                Some(Operation::DeleteItem {
+                    list_index,
+                    index,
+                    item,
+                })
+            }
+            Operation::Edit { list_index, item } => {
+                let group_list = self.group_list.items.get_mut(list_index)?;
+                let edited = group_list
+                    .list
+                    .items
+                    .iter_mut()
+                    .find(|edited| edited.id == item.id)?;
+                let current = std::mem::replace(edited, item);
+                Some(Operation::Edit {
+                    list_index,
+                    item: current,
+                })
+            }
+            Operation::Move {
+                list_index,
+                from,
+                to,
+            } => {
+                match list_index {
+                    Some(index) => {
+                        swap_back(&mut self.group_list.items.get_mut(index)?.list, from, to)
+                    }
+                    None => swap_back(&mut self.group_list, from, to),
+                }
+                Some(Operation::Move {
+                    list_index,
+                    from: to,
+                    to: from,
+                })
+            }
+            Operation::Reinsert {
+                list_index,
+                from,
+                to,
+            } => {
+                match list_index {
+                    Some(index) => {
+                        reinsert_back(&mut self.group_list.items.get_mut(index)?.list, from, to)
+                    }
+                    None => reinsert_back(&mut self.group_list, from, to),
+                }
+                Some(Operation::Reinsert {
+                    list_index,
+                    from: to,
+                    to: from,
+                })
+            }
+            Operation::Swap {
+                first_list,
+                first,
+                second_list,
+                second,
+            } => {
+                self.exchange_items((first_list, first), (second_list, second))?;
+                Some(Operation::Swap {
+                    first_list,
+                    first,
+                    second_list,
+                    second,
+                })
+            }
+            Operation::Merge {
+                source,
+                target,
+                count,
+                mut list,
+            } => {
+                let merged = if target > source { target - 1 } else { target };
+                let items = &mut self.group_list.items.get_mut(merged)?.list;
+                let split = items.items.len().checked_sub(count)?;
+                list.list.items = items.items.split_off(split);
+                items.clamp_selection();
+                list.list.clamp_selection();
+                let source = source.min(self.group_list.items.len());
+                self.group_list.items.insert(source, list);
+                self.active_list = None;
+                self.group_list.state.select(Some(source));
+                Some(Operation::Unmerge { source, target })
+            }
+            Operation::Unmerge { source, target } => self.merge_lists(source, target),
+            Operation::Promote {
+                list_index,
+                index,
+                item,
+            } => {
+                if list_index + 1 >= self.group_list.items.len() {
+                    return None;
+                }
+                self.group_list.items.remove(list_index + 1);
+                let list = &mut self.group_list.items[list_index].list;
+                let index = index.min(list.items.len());
+                list.items.insert(index, item);
+                list.state.select(Some(index));
+                self.active_list = Some(list_index);
+                self.group_list.state.select(Some(list_index));
+                Some(Operation::Unpromote { list_index, index })
+            }
+            Operation::Unpromote { list_index, index } => self.promote_item(list_index, index),
+            Operation::Batch { operations } => {
+                let operations = operations
+                    .into_iter()
+                    .rev()
+                    .filter_map(|operation| self.revert(operation))
+                    .collect();
+                Some(Operation::Batch { operations })
+            }
+            Operation::Transfer {
+                from_list,
+                from,
+                to_list,
+                to,
+            } => {
+                if from_list >= self.group_list.items.len() {
+                    return None;
+                }
+                let list = &mut self.group_list.items.get_mut(to_list)?.list;
+                if to >= list.items.len() {
+                    return None;
+                }
+                let item = list.items.remove(to);
+                list.clamp_selection();
+                let list = &mut self.group_list.items[from_list].list;
+                let from = from.min(list.items.len());
+                list.items.insert(from, item);
+                if self.active_list == Some(to_list) {
+                    list.state.select(Some(from));
+                    self.active_list = Some(from_list);
+                    self.group_list.state.select(Some(from_list));
+                }
+                Some(Operation::Transfer {
+                    from_list: to_list,
+                    from: to,
+                    to_list: from_list,
+                    to: from,
+                })
+            }
+        }
+    }
+}
+
+impl App {
+    fn item_position(&self, id: u64) -> Option<(usize, usize)> {
+        self.group_list
+            .items
+            .iter()
+            .enumerate()
+            .find_map(|(list_index, list)| {
+                let index = list.list.items.iter().position(|item| item.id == id)?;
+                Some((list_index, index))
+            })
+    }
+}
+
+fn swap_back<T>(list: &mut StatefulList<T>, from: usize, to: usize) {
+    if from < list.items.len() && to < list.items.len() {
+        list.items.swap(from, to);
+        if list.state.selected() == Some(to) {
+            list.state.select(Some(from));
+        }
+    }
+}
+
+fn reinsert_back<T>(list: &mut StatefulList<T>, from: usize, to: usize) {
+    if to < list.items.len() {
+        let item = list.items.remove(to);
+        let from = from.min(list.items.len());
+        list.items.insert(from, item);
+        if list.state.selected() == Some(to) {
+            list.state.select(Some(from));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::app::{app_with_items, SortMode, Subtask};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn titles(app: &App) -> Vec<String> {
+        app.group_list.items[0]
+            .list
+            .items
+            .iter()
+            .map(|item| item.title.clone())
+            .collect()
+    }
+
+    fn restart(app: &mut App) -> App {
+        app.store_history();
+        let saved = app.to_toml().expect("app saves");
+        App::loaded(toml::from_str(&saved).expect("app loads"), "test.toml")
+    }
+
+    #[test]
+    fn undo_history_survives_a_restart() {
+        let mut app = app_with_items(&["one", "two"]);
+        app.config.persist_undo = true;
+        app.group_list.items[0].list.state.select(Some(0));
+        app.event(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(titles(&app), vec!["two"]);
+
+        let mut app = restart(&mut app);
+        assert_eq!(app.undo.len(), 1);
+        app.undo();
+        assert_eq!(titles(&app), vec!["one", "two"]);
+        assert!(app.bin.is_empty());
+    }
+
+    #[test]
+    fn every_operation_survives_a_restart() {
+        let mut app = app_with_items(&["one"]);
+        app.config.persist_undo = true;
+        let item = app.group_list.items[0].list.items[0].clone();
+        let list = app.group_list.items[0].clone();
+        let operations = vec![
+            Operation::DeleteGroup {
+                index: 0,
+                list: list.clone(),
+            },
+            Operation::InsertGroup { id: list.id },
+            Operation::Rename {
+                id: list.id,
+                name: "Office".to_string(),
+            },
+            Operation::DeleteItem {
+                list_index: 0,
+                index: 0,
+                item: item.clone(),
+            },
+            Operation::InsertItem { id: item.id },
+            Operation::Edit {
+                list_index: 0,
+                item: item.clone(),
+            },
+            Operation::Move {
+                list_index: Some(0),
+                from: 0,
+                to: 1,
+            },
+            Operation::Reinsert {
+                list_index: None,
+                from: 1,
+                to: 0,
+            },
+            Operation::Swap {
+                first_list: 0,
+                first: 0,
+                second_list: 1,
+                second: 0,
+            },
+            Operation::Merge {
+                source: 1,
+                target: 0,
+                count: 1,
+                list,
+            },
+            Operation::Unmerge {
+                source: 1,
+                target: 0,
+            },
+            Operation::Promote {
+                list_index: 0,
+                index: 0,
+                item,
+            },
+            Operation::Unpromote {
+                list_index: 0,
+                index: 0,
+            },
+            Operation::Batch {
+                operations: vec![
+                    Operation::InsertItem { id: 1 },
+                    Operation::InsertItem { id: 2 },
+                ],
+            },
+            Operation::Transfer {
+                from_list: 0,
+                from: 0,
+                to_list: 1,
+                to: 0,
+            },
+        ];
+        let count = operations.len();
+        for operation in operations {
+            app.record(operation);
+        }
+
+        // Loading renumbers the items, so only the history is compared.
+        fn history(app: &mut App) -> String {
+            app.store_history();
+            let saved = app.to_toml().expect("app saves");
+            saved[saved.find("[[history]]").expect("history is saved")..].to_string()
+        }
+        let saved = history(&mut app);
+        let mut app = restart(&mut app);
+        assert_eq!(app.undo.len(), count);
+        assert_eq!(history(&mut app), saved);
+    }
+
+    #[test]
+    fn undo_history_is_dropped_without_persist_undo() {
+        let mut app = app_with_items(&["one", "two"]);
+        app.group_list.items[0].list.state.select(Some(0));
+        app.event(KeyCode::Char('d'), KeyModifiers::CONTROL);
+
+        let mut app = restart(&mut app);
+        assert!(app.undo.is_empty());
+        app.undo();
+        assert_eq!(titles(&app), vec!["two"]);
+    }
+
+    fn names(app: &App) -> Vec<String> {
+        app.group_list
+            .items
+            .iter()
+            .map(|list| list.name.clone())
+            .collect()
+    }
+
+    fn select(app: &mut App, index: usize) {
+        app.group_list.items[0].list.state.select(Some(index));
+    }
+
+    #[test]
+    fn a_second_cut_is_refused() {
+        let mut app = app_with_items(&["one", "two", "three"]);
+        select(&mut app, 0);
+        app.event(KeyCode::Char('x'), KeyModifiers::NONE);
+        app.event(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(titles(&app), vec!["two", "three"]);
+
+        select(&mut app, 1);
+        app.event(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert_eq!(titles(&app), vec!["two", "one", "three"]);
+        assert!(app.cut.is_none());
+    }
+
+    #[test]
+    fn undo_puts_a_pending_cut_back() {
+        let mut app = app_with_items(&["one", "two"]);
+        select(&mut app, 0);
+        app.event(KeyCode::Char('x'), KeyModifiers::NONE);
+        app.undo();
+        assert_eq!(titles(&app), vec!["one", "two"]);
+        assert!(app.cut.is_none());
+    }
+
+    #[test]
+    fn undo_moves_a_pasted_item_back() {
+        let mut app = app_with_items(&["one", "two", "three"]);
+        select(&mut app, 0);
+        app.event(KeyCode::Char('x'), KeyModifiers::NONE);
+        select(&mut app, 2);
+        app.event(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert_eq!(titles(&app), vec!["two", "three", "one"]);
+        app.undo();
+        assert_eq!(titles(&app), vec!["one", "two", "three"]);
+        app.redo();
+        assert_eq!(titles(&app), vec!["two", "three", "one"]);
+    }
+
+    #[test]
+    fn undo_moves_a_pasted_list_back() {
+        let mut app = app_with_items(&[]);
+        app.group_list.add(GroupList::new("Home".to_string()));
+        app.group_list.add(GroupList::new("Errands".to_string()));
+        app.active_list = None;
+        app.group_list.state.select(Some(0));
+        app.event(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(names(&app), vec!["Home", "Errands"]);
+        app.undo();
+        assert_eq!(names(&app), vec!["Work", "Home", "Errands"]);
+
+        app.group_list.state.select(Some(0));
+        app.event(KeyCode::Char('x'), KeyModifiers::NONE);
+        app.group_list.state.select(Some(2));
+        app.event(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert_eq!(names(&app), vec!["Home", "Errands", "Work"]);
+        app.undo();
+        assert_eq!(names(&app), vec!["Work", "Home", "Errands"]);
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            app.event(KeyCode::Char(c), KeyModifiers::NONE);
+        }
+    }
+
+    #[test]
+    fn undo_reverts_an_edit() {
+        let mut app = app_with_items(&["one", "two"]);
+        select(&mut app, 0);
+        app.event(KeyCode::Char('e'), KeyModifiers::CONTROL);
+        type_text(&mut app, " more");
+        app.event(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(titles(&app), vec!["one more", "two"]);
+
+        app.undo();
+        assert_eq!(titles(&app), vec!["one", "two"]);
+        app.redo();
+        assert_eq!(titles(&app), vec!["one more", "two"]);
+    }
+
+    #[test]
+    fn undo_reverts_a_list_rename() {
+        let mut app = app_with_items(&["one"]);
+        app.active_list = None;
+        app.event(KeyCode::Char('r'), KeyModifiers::NONE);
+        app.event(KeyCode::Backspace, KeyModifiers::NONE);
+        type_text(&mut app, "ld");
+        app.event(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(names(&app), vec!["World"]);
+
+        app.undo();
+        assert_eq!(names(&app), vec!["Work"]);
+        app.redo();
+        assert_eq!(names(&app), vec!["World"]);
+    }
+
+    #[test]
+    fn undo_reverts_an_edit_and_a_later_delete() {
+        let mut app = app_with_items(&["one", "two"]);
+        select(&mut app, 1);
+        app.event(KeyCode::Char('e'), KeyModifiers::CONTROL);
+        type_text(&mut app, "!");
+        app.event(KeyCode::Enter, KeyModifiers::NONE);
+        select(&mut app, 0);
+        app.event(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(titles(&app), vec!["two!"]);
+
+        app.undo();
+        assert_eq!(titles(&app), vec!["one", "two!"]);
+        app.undo();
+        assert_eq!(titles(&app), vec!["one", "two"]);
+    }
+
+    #[test]
+    fn undo_splits_merged_lists_again() {
+        let mut app = app_with_items(&["one", "two"]);
+        let mut home = GroupList::new("Home".to_string());
+        home.list.add(Item {
+            id: app.new_id(),
+            title: "three".to_string(),
+            ..Item::default()
+        });
+        app.group_list.add(home);
+        app.merge_groups(0, 1);
+        assert_eq!(names(&app), vec!["Home"]);
+        assert_eq!(titles(&app), vec!["three", "one", "two"]);
+
+        app.undo();
+        assert_eq!(names(&app), vec!["Work", "Home"]);
+        assert_eq!(titles(&app), vec!["one", "two"]);
+        assert_eq!(app.group_list.items[1].list.items.len(), 1);
+
+        app.redo();
+        assert_eq!(names(&app), vec!["Home"]);
+        assert_eq!(titles(&app), vec!["three", "one", "two"]);
+    }
+
+    #[test]
+    fn undo_puts_a_list_merged_upwards_back() {
+        let mut app = app_with_items(&["one"]);
+        app.group_list.add(GroupList::new("Home".to_string()));
+        app.merge_groups(1, 0);
+        assert_eq!(names(&app), vec!["Work"]);
+        app.undo();
+        assert_eq!(names(&app), vec!["Work", "Home"]);
+        assert_eq!(titles(&app), vec!["one"]);
+    }
+
+    #[test]
+    fn promoting_keeps_the_time_and_can_be_undone() {
+        let mut app = app_with_items(&["one", "project"]);
+        {
+            let item = &mut app.group_list.items[0].list.items[1];
+            item.duration = 90_000;
+            item.desc = "notes".to_string();
+            item.tags = vec!["client".to_string()];
+            item.subtasks = vec![
+                Subtask {
+                    title: "plan".to_string(),
+                    done: true,
+                },
+                Subtask {
+                    title: "build".to_string(),
+                    done: false,
+                },
+            ];
+        }
+        select(&mut app, 1);
+        app.event(KeyCode::Char('e'), KeyModifiers::ALT);
+        assert_eq!(names(&app), vec!["Work", "project"]);
+        let promoted = &app.group_list.items[1].list.items;
+        assert_eq!(promoted.len(), 3);
+        assert_eq!(promoted[0].duration, 90_000);
+        assert_eq!(promoted[0].desc, "notes");
+        assert!(promoted[0].subtasks.is_empty());
+        assert!(promoted[1].done());
+        assert!(promoted.iter().all(|item| item.tags == vec!["client"]));
+
+        app.undo();
+        assert_eq!(names(&app), vec!["Work"]);
+        assert_eq!(titles(&app), vec!["one", "project"]);
+        assert_eq!(app.group_list.items[0].list.items[1].subtasks.len(), 2);
+
+        app.redo();
+        assert_eq!(names(&app), vec!["Work", "project"]);
+        assert_eq!(titles(&app), vec!["one"]);
+    }
+
+    #[test]
+    fn promoting_a_fresh_item_only_adds_its_subtasks() {
+        let mut app = app_with_items(&["project"]);
+        app.group_list.items[0].list.items[0].subtasks = vec![Subtask {
+            title: "plan".to_string(),
+            done: false,
+        }];
+        select(&mut app, 0);
+        app.event(KeyCode::Char('e'), KeyModifiers::ALT);
+        let promoted = &app.group_list.items[1].list.items;
+        assert_eq!(promoted.len(), 1);
+        assert_eq!(promoted[0].title, "plan");
+    }
+
+    fn add_item(app: &mut App, title: &str) {
+        app.event(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        type_text(app, title);
+        app.event(KeyCode::Enter, KeyModifiers::NONE);
+    }
+
+    fn status(app: &App, index: usize) -> (bool, bool, bool) {
+        let item = &app.group_list.items[0].list.items[index];
+        (item.started(), item.paused, item.done())
+    }
+
+    #[test]
+    fn adding_and_status_changes_undo_in_order() {
+        let mut app = app_with_items(&["one"]);
+        add_item(&mut app, "two");
+        assert_eq!(titles(&app), vec!["one", "two"]);
+        select(&mut app, 1);
+        app.event(KeyCode::Char('s'), KeyModifiers::ALT);
+        app.event(KeyCode::Char('p'), KeyModifiers::ALT);
+        app.event(KeyCode::Char('d'), KeyModifiers::ALT);
+        assert_eq!(status(&app, 1), (true, true, true));
+
+        app.undo();
+        assert_eq!(status(&app, 1), (true, true, false));
+        app.undo();
+        assert_eq!(status(&app, 1), (true, false, false));
+        app.redo();
+        assert_eq!(status(&app, 1), (true, true, false));
+        app.undo();
+        app.undo();
+        assert_eq!(status(&app, 1), (false, false, false));
+        app.undo();
+        assert_eq!(titles(&app), vec!["one"]);
+
+        app.redo();
+        assert_eq!(titles(&app), vec!["one", "two"]);
+        app.redo();
+        assert_eq!(status(&app, 1), (true, false, false));
+    }
+
+    #[test]
+    fn space_steps_can_be_undone_one_at_a_time() {
+        let mut app = app_with_items(&["one"]);
+        app.config.confirm_restart_done = false;
+        select(&mut app, 0);
+        for _ in 0..3 {
+            app.event(KeyCode::Char(' '), KeyModifiers::NONE);
+        }
+        assert_eq!(status(&app, 0), (true, false, true));
+        app.event(KeyCode::Char(' '), KeyModifiers::NONE);
+        assert_eq!(status(&app, 0), (false, false, false));
+
+        app.undo();
+        assert_eq!(status(&app, 0), (true, false, true));
+        app.undo();
+        assert_eq!(status(&app, 0), (true, true, false));
+        app.undo();
+        assert_eq!(status(&app, 0), (true, false, false));
+        app.undo();
+        assert_eq!(status(&app, 0), (false, false, false));
+        assert!(app.undo.is_empty());
+    }
+
+    #[test]
+    fn undoing_an_add_finds_the_item_after_sorting() {
+        let mut app = app_with_items(&["beta", "gamma"]);
+        app.group_list.items[0].sort_mode = SortMode::Title;
+        add_item(&mut app, "alpha");
+        assert_eq!(titles(&app), vec!["alpha", "beta", "gamma"]);
+        select(&mut app, 2);
+        app.event(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(titles(&app), vec!["alpha", "beta"]);
+
+        app.undo();
+        app.undo();
+        assert_eq!(titles(&app), vec!["beta", "gamma"]);
+        app.redo();
+        assert_eq!(titles(&app), vec!["alpha", "beta", "gamma"]);
+        app.redo();
+        assert_eq!(titles(&app), vec!["alpha", "beta"]);
+    }
+
+    #[test]
+    fn undoing_a_new_list_removes_it_by_id() {
+        let mut app = app_with_items(&[]);
+        app.active_list = None;
+        add_item(&mut app, "Home");
+        app.group_list.state.select(Some(0));
+        app.event(KeyCode::Char('x'), KeyModifiers::NONE);
+        app.group_list.state.select(Some(1));
+        app.event(KeyCode::Char('p'), KeyModifiers::NONE);
+        assert_eq!(names(&app), vec!["Home", "Work"]);
+
+        app.undo();
+        app.undo();
+        assert_eq!(names(&app), vec!["Work"]);
+        app.redo();
+        assert_eq!(names(&app), vec!["Work", "Home"]);
+    }
+
+    #[test]
+    fn undoing_a_new_list_keeps_an_older_one_with_the_same_name() {
+        let mut app = app_with_items(&["one"]);
+        app.active_list = None;
+        add_item(&mut app, "Work");
+        assert_eq!(names(&app), vec!["Work", "Work"]);
+
+        app.undo();
+        assert_eq!(names(&app), vec!["Work"]);
+        assert_eq!(titles(&app), vec!["one"]);
+    }
+
+    #[test]
+    fn undoing_a_new_list_finds_it_after_a_rename() {
+        let mut app = app_with_items(&[]);
+        app.active_list = None;
+        add_item(&mut app, "Home");
+        app.group_list.items[1].name = "House".to_string();
+
+        app.undo();
+        assert_eq!(names(&app), vec!["Work"]);
+        assert!(app.undo.is_empty());
+    }
+
+    #[test]
+    fn undoing_a_new_list_above_the_open_one_keeps_it_open() {
+        let mut app = app_with_items(&["one"]);
+        app.active_list = None;
+        add_item(&mut app, "Home");
+        let home = app.group_list.items.remove(1);
+        app.group_list.items.insert(0, home);
+        app.active_list = Some(1);
+
+        app.undo();
+        assert_eq!(names(&app), vec!["Work"]);
+        assert_eq!(app.active_list, Some(0));
+    }
+
+    #[test]
+    fn a_change_that_cannot_be_undone_stays_on_the_stack() {
+        let mut app = app_with_items(&["one"]);
+        app.record(Operation::InsertItem { id: 99 });
+        app.undo();
+        assert_eq!(app.undo.len(), 1);
+        assert!(app.redo.is_empty());
+    }
+
+    #[test]
+    fn captured_items_are_undone_with_their_new_inbox() {
+        let mut app = app_with_items(&["one"]);
+        app.event(KeyCode::Char('n'), KeyModifiers::ALT);
+        type_text(&mut app, "idea");
+        app.event(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(names(&app), vec!["Work", "Inbox"]);
+        app.undo();
+        assert_eq!(names(&app), vec!["Work"]);
+        app.redo();
+        assert_eq!(names(&app), vec!["Work", "Inbox"]);
+        assert_eq!(app.group_list.items[1].list.items[0].title, "idea");
+    }
+}
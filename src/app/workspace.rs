@@ -0,0 +1,202 @@
+use crate::app::app::App;
+#[cfg(feature = "sqlite")]
+use crate::app::sqlite;
+
+use chrono::Local;
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+
+pub const DB_PATH: &str = "db.toml";
+
+impl App {
+    /// Reads the app from `path`, starting a fresh one if the file does not exist yet.
+    pub fn load(path: &str) -> Result<App, Box<dyn Error>> {
+        #[cfg(feature = "sqlite")]
+        {
+            if sqlite::is_sqlite(path) {
+                return App::load_sqlite(path);
+            }
+        }
+        let app: App = match fs::read_to_string(path) {
+            Ok(db) => toml::from_str(&db)?,
+            // No database yet, so this is the first launch.
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                let mut app = App::new("Todo-Timer".to_string());
+                app.show_onboarding = true;
+                app
+            }
+            Err(err) => return Err(err.into()),
+        };
+        Ok(App::loaded(app, path))
+    }
+
+    /// Prepares a freshly read app for use.
+    pub fn loaded(mut app: App, path: &str) -> App {
+        app.path = path.to_string();
+        app.last_input = Some(Local::now());
+        app.repairs = app.validate_and_repair();
+        app.assign_missing_ids();
+        app.clamp_selection();
+        app.sort_lists();
+        app.prune_bin();
+        app.restore_history();
+        app
+    }
+
+    pub fn save(&mut self) -> Result<(), Box<dyn Error>> {
+        self.store_history();
+        #[cfg(feature = "sqlite")]
+        {
+            if sqlite::is_sqlite(&self.path) {
+                let path = self.path.clone();
+                return self.save_sqlite(&path);
+            }
+        }
+        // Written next to the database first, so a crash mid-write cannot
+        // leave it half saved.
+        let tmp = format!("{}.tmp", self.path);
+        fs::write(&tmp, self.to_toml()?)?;
+        fs::rename(&tmp, &self.path)?;
+        self.mark_saved();
+        Ok(())
+    }
+
+    /// Saves the current database and swaps in the next configured workspace.
+    pub fn switch_workspace(&mut self) {
+        let workspaces = self.config.workspaces.clone();
+        if workspaces.is_empty() {
+            self.notify("No workspaces configured".to_string());
+            return;
+        }
+        let next = workspaces
+            .iter()
+            .position(|workspace| workspace.path == self.path)
+            .map_or(0, |index| (index + 1) % workspaces.len());
+        let workspace = &workspaces[next];
+
+        if let Err(err) = self.save() {
+            self.notify(format!("Could not save {}: {}", self.path, err));
+            return;
+        }
+        match App::load(&workspace.path) {
+            Ok(mut app) => {
+                if app.config.workspaces.is_empty() {
+                    app.config.workspaces = workspaces.clone();
+                }
+                // Someone switching workspaces knows the app already.
+                app.show_onboarding = false;
+                app.seen_onboarding = true;
+                app.notify(format!("Switched to workspace \"{}\"", workspace.name));
+                *self = app;
+            }
+            Err(err) => {
+                self.notify(format!("Could not load {}: {}", workspace.path, err));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::app::{App, Item, Stop, SubTimer, Subtask};
+    use chrono::Local;
+
+    /// Saves and loads the app and checks that saving again gives the same file.
+    fn assert_round_trip(app: &App) -> App {
+        let saved = app.to_toml().expect("app saves");
+        let loaded = App::loaded(toml::from_str(&saved).expect("app loads"), "test.toml");
+        assert_eq!(loaded.to_toml().expect("app saves again"), saved);
+        loaded
+    }
+
+    fn app_with_lists() -> App {
+        let mut app = App::new("Test".to_string());
+        let mut work = app.new_list("Work".to_string());
+        work.list.add(Item {
+            id: app.new_id(),
+            title: "Plain".to_string(),
+            duration: 1_000,
+            order: 1,
+            ..Item::default()
+        });
+        work.list.add(Item {
+            id: app.new_id(),
+            title: "Nested".to_string(),
+            start_at: Some(Local::now()),
+            tags: vec!["client".to_string()],
+            subtasks: vec![Subtask {
+                title: "First step".to_string(),
+                done: false,
+            }],
+            stops: vec![Stop {
+                at: Local::now(),
+                duration: 500,
+                note: "break".to_string(),
+            }],
+            timers: vec![SubTimer {
+                name: "Calls".to_string(),
+                duration: 200,
+                running: false,
+            }],
+            order: 2,
+            ..Item::default()
+        });
+        app.group_list.add(work);
+        let empty = app.new_list("Empty".to_string());
+        app.group_list.add(empty);
+        app
+    }
+
+    #[test]
+    fn empty_app_round_trips() {
+        assert_round_trip(&App::new("Test".to_string()));
+    }
+
+    #[test]
+    fn app_with_lists_round_trips() {
+        let loaded = assert_round_trip(&app_with_lists());
+        assert_eq!(loaded.group_list.items.len(), 2);
+        let items = &loaded.group_list.items[0].list.items;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].subtasks.len(), 1);
+        assert_eq!(items[1].stops.len(), 1);
+        assert_eq!(items[1].timers.len(), 1);
+    }
+
+    #[test]
+    fn app_with_bin_round_trips() {
+        let mut app = app_with_lists();
+        let item = app.group_list.items[0].list.items.remove(1);
+        app.bin_item(0, 1, item);
+        let list = app.group_list.items.remove(1);
+        app.bin_group(1, list);
+
+        let loaded = assert_round_trip(&app);
+        assert_eq!(loaded.bin.len(), 2);
+        assert_eq!(
+            loaded.bin[0].item.as_ref().map(|item| item.title.as_str()),
+            Some("Nested")
+        );
+        assert_eq!(
+            loaded.bin[1].list.as_ref().map(|list| list.name.as_str()),
+            Some("Empty")
+        );
+    }
+
+    #[test]
+    fn saving_replaces_the_file_without_leaving_a_temporary_one() {
+        let path =
+            std::env::temp_dir().join(format!("todo-timer-save-{}.toml", std::process::id()));
+        let path = path.to_str().expect("temp path is valid").to_string();
+        std::fs::write(&path, "broken").expect("file is written");
+
+        let mut app = app_with_lists();
+        app.path = path.clone();
+        app.save().expect("app saves");
+        assert!(!std::path::Path::new(&format!("{}.tmp", path)).exists());
+        let loaded = App::load(&path).expect("app loads");
+        assert_eq!(loaded.group_list.items.len(), 2);
+        std::fs::remove_file(&path).expect("file is removed");
+    }
+}
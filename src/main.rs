@@ -1,14 +1,18 @@
 mod app;
 use app::app::App;
+use app::confirm::Confirm;
+use app::workspace::DB_PATH;
 
 use crossterm::{
+    cursor::Show,
     event::{self, DisableMouseCapture, Event as CEvent, KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 
+use std::env;
 use std::error::Error;
-use std::fs;
+use std::panic;
 use std::{
     io::{stdout, Write},
     sync::mpsc,
@@ -20,10 +24,48 @@ use tui::Terminal;
 
 enum Event<I> {
     Input(I),
+    Resize,
     Tick(Duration),
 }
 
+fn restore_terminal() -> Result<(), Box<dyn Error>> {
+    disable_raw_mode()?;
+    execute!(stdout(), LeaveAlternateScreen, DisableMouseCapture, Show)?;
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
+    let mut app = App::load(DB_PATH)?;
+
+    if let Some("report") = env::args().nth(1).as_deref() {
+        match env::args().nth(2) {
+            Some(name) => match app.list_report(&name) {
+                Some(report) => print!("{}", report),
+                None => {
+                    eprintln!("No list called \"{}\"", name);
+                    std::process::exit(1);
+                }
+            },
+            None => print!("{}", app.report()),
+        }
+        return Ok(());
+    }
+
+    let args = env::args().collect::<Vec<_>>();
+    if let Some(name) = args
+        .iter()
+        .position(|arg| arg == "--list")
+        .and_then(|index| args.get(index + 1))
+    {
+        app.open_list_named(name);
+    }
+
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        default_hook(info);
+    }));
+
     enable_raw_mode()?;
     let mut stdout = stdout();
     execute!(stdout, EnterAlternateScreen)?;
@@ -41,8 +83,12 @@ fn main() -> Result<(), Box<dyn Error>> {
         loop {
             // poll for tick rate duration, if no events, sent tick event.
             if event::poll(tick_rate - last_tick.elapsed()).unwrap() {
-                if let CEvent::Key(KeyEvent { code, modifiers }) = event::read().unwrap() {
-                    tx.send(Event::Input(KeyEvent { code, modifiers })).unwrap();
+                match event::read().unwrap() {
+                    CEvent::Key(KeyEvent { code, modifiers }) => {
+                        tx.send(Event::Input(KeyEvent { code, modifiers })).unwrap();
+                    }
+                    CEvent::Resize(_, _) => tx.send(Event::Resize).unwrap(),
+                    _ => {}
                 }
             }
             if last_tick.elapsed() >= tick_rate {
@@ -52,11 +98,6 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     });
 
-    let mut app: App = match fs::read_to_string("db.toml") {
-        Ok(db) => toml::from_str(&db).unwrap(),
-        Err(_) => App::new("Todo-Timer".to_string()),
-    };
-
     terminal.clear()?;
 
     let mut time_passed = Duration::ZERO;
@@ -64,31 +105,57 @@ fn main() -> Result<(), Box<dyn Error>> {
         terminal.draw(|f| app.draw(f))?;
         match rx.recv()? {
             Event::Input(event) => match (event.code, event.modifiers) {
-                (KeyCode::Char('q'), KeyModifiers::CONTROL) => {
-                    disable_raw_mode()?;
-                    execute!(
-                        terminal.backend_mut(),
-                        LeaveAlternateScreen,
-                        DisableMouseCapture
-                    )?;
-                    terminal.show_cursor()?;
+                (KeyCode::Char('q'), KeyModifiers::CONTROL)
+                    if app.config.confirm_quit && !app.quit_pending() =>
+                {
+                    app.confirm = Some(Confirm::Quit);
+                }
+                (KeyCode::Char('q'), KeyModifiers::CONTROL) | (KeyCode::Char('y'), _)
+                    if event.code != KeyCode::Char('y') || app.quit_pending() =>
+                {
+                    restore_terminal()?;
 
-                    fs::write("db.toml", toml::to_string(&app).unwrap())?;
+                    if app.config.stop_timers_on_quit {
+                        app.pause_all();
+                    }
+                    app.save()?;
 
                     break Ok(());
                 }
+                (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                    let message = match app.save() {
+                        Ok(()) => "Saved".to_string(),
+                        Err(err) => format!("Could not save {}: {}", app.path, err),
+                    };
+                    app.notify(message);
+                    time_passed = Duration::ZERO;
+                }
+                (KeyCode::Char('l'), KeyModifiers::CONTROL) => {
+                    // Wipes stray output of other programs, the next draw
+                    // repaints everything.
+                    terminal.clear()?;
+                }
                 (x, modi) => {
                     app.event(x, modi);
                 }
             },
+            Event::Resize => {
+                terminal.autoresize()?;
+                app.resize();
+            }
             Event::Tick(duration) => {
                 if time_passed > auto_safe_interval {
-                    fs::write("db.toml", toml::to_string(&app).unwrap())?;
+                    app.save()?;
                     time_passed = Duration::ZERO;
                 } else {
-                    time_passed = time_passed + duration;
+                    time_passed += duration;
+                }
+                app.add_time(duration);
+                let reminder = app.reminder_bell();
+                if app.idle_bell() || reminder {
+                    write!(terminal.backend_mut(), "\x07")?;
+                    terminal.backend_mut().flush()?;
                 }
-                app.add_time(duration)
             }
         };
     }